@@ -1,7 +1,8 @@
 mod arrayref;
 mod arrays;
-// mod layout;
 mod backends;
+mod error;
+mod layout;
 mod ownership;
 mod storage;
 mod subtraits;
@@ -9,5 +10,6 @@ mod subtraits;
 pub use arrayref::*;
 pub use arrays::*;
 pub use backends::*;
-// pub use layout::*;
+pub use error::*;
+pub use layout::*;
 pub use subtraits::*;
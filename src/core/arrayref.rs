@@ -2,6 +2,4 @@ mod impls;
 mod structs;
 mod traits;
 
-pub use impls::*;
 pub use structs::*;
-pub use traits::*;
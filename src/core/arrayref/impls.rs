@@ -29,12 +29,735 @@
 
 // impl<L: Layout, S: Storage> NdArray<L, S, RawArrayRefBase<L, S>> for ArrayRefBase<L, S> {}
 
-use crate::core::{Backend, Layout};
+use std::{
+    iter::Sum,
+    marker::PhantomData,
+    ops::{Index, IndexMut, Mul},
+    slice,
+};
 
-use super::RawArrayRefBase;
+use crate::core::{
+    ArrayBase, ArrayViewBase, ArrayViewBaseMut, AxisSelector, Backend, Banded, Broadcast, Dense,
+    IndexAxis, IndexBuf, IndexFromSlice, Indices, InsertAxis, Layout, NdIndex, PermuteAxes,
+    Pitched, PointerStorage, RawArrayRefSized, RemoveAxis, ShapeError, Slice, Strided, Tiled,
+    VecBackend,
+};
+
+use super::{ArrayRefBase, RawArrayRefBase};
 
 impl<L: Layout, B: Backend> RawArrayRefBase<L, B> {
+    /// Return the layout of the array.
+    pub fn layout(&self) -> &L {
+        &self.layout
+    }
+
+    /// Return the total number of elements in the array.
     pub fn len(&self) -> usize {
         self.layout.size()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return an iterator over every index of the array, in row-major order.
+    ///
+    /// The iterator does not borrow the array, so each index can be used to modify it.
+    /// Indices outside the elements a layout stores are included, and panic if used to
+    /// index the array; see [`Layout::indices`].
+    pub fn indices(&self) -> Indices<L::Indexer>
+    where
+        L::Indexer: IndexBuf,
+    {
+        self.layout.indices()
+    }
+
+    /// Return a pointer to the "origin" of the array.
+    pub fn as_ptr(&self) -> *const B::Elem {
+        unsafe { self.storage.as_ptr() }
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut B::Elem {
+        unsafe { self.storage.as_ptr() }
+    }
+
+    /// The array at `index` along `axis`, with that axis removed; see [`IndexAxis`].
+    ///
+    /// **Panics** if `axis` selects no axis, or if `index` is out of bounds.
+    pub(crate) fn raw_index_axis(
+        &self,
+        axis: impl AxisSelector<L>,
+        index: usize,
+    ) -> RawArrayRefSized<L::Smaller, B>
+    where
+        L: IndexAxis,
+    {
+        let axis = axis.select(&self.layout).unwrap_or_else(|e| panic!("{e}"));
+        let (layout, offset) = self.layout.index_axis(axis, index);
+        RawArrayRefBase {
+            layout,
+            // The new origin is an element of the array, at `index` along `axis`.
+            storage: unsafe { self.storage.offset(offset) },
+            _dst: [],
+        }
+    }
+
+    /// The array with its axes reordered; see [`PermuteAxes`].
+    ///
+    /// **Panics** if any of `axes` selects no axis, or if they are not a permutation.
+    pub(crate) fn raw_permuted_axes<S: AxisSelector<L>>(
+        &self,
+        axes: &[S],
+    ) -> RawArrayRefSized<L::Permuted, B>
+    where
+        L: PermuteAxes,
+    {
+        RawArrayRefBase {
+            layout: self
+                .layout
+                .permuted_axes_by(axes)
+                .unwrap_or_else(|e| panic!("{e}")),
+            // The origin stays where it is.
+            storage: unsafe { self.storage.offset(0) },
+            _dst: [],
+        }
+    }
+
+    /// The array with a new axis of length one; see [`InsertAxis`].
+    pub(crate) fn raw_insert_axis(&self, axis: usize) -> RawArrayRefSized<L::Larger, B>
+    where
+        L: InsertAxis,
+    {
+        RawArrayRefBase {
+            layout: self.layout.insert_axis(axis),
+            // The origin stays where it is.
+            storage: unsafe { self.storage.offset(0) },
+            _dst: [],
+        }
+    }
+}
+
+impl<const N: usize, B: Backend> RawArrayRefBase<Strided<N>, B> {
+    /// Reverse the order of the elements along `axis`, without moving any of them.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn invert_axis(&mut self, axis: usize) {
+        let offset = self.layout.invert_axis(axis);
+        // The new origin is the last element along `axis`, which is within the array.
+        self.storage = unsafe { self.storage.offset(offset) };
+    }
+
+    /// Restrict `axis` to `slice`, without moving any elements.
+    ///
+    /// **Panics** if `axis` is out of bounds, or if `slice` starts or ends out of bounds.
+    pub fn slice_axis_inplace(&mut self, axis: usize, slice: impl Into<Slice>) {
+        let offset = self.layout.slice_axis(axis, slice.into());
+        // The new origin is the first element of the slice, which is within the array.
+        self.storage = unsafe { self.storage.offset(offset) };
+    }
+}
+
+impl<L: Layout, B: Backend> ArrayRefBase<L, B> {
+    /// Return a view of the array with `axis` restricted to `slice`.
+    ///
+    /// A negative step reverses the axis, so `arr.slice_axis(0, Slice::from(..).step_by(-1))`
+    /// views `arr` upside down.
+    ///
+    /// **Panics** if `axis` is out of bounds, or if `slice` starts or ends out of bounds.
+    pub fn slice_axis<const N: usize>(
+        &self,
+        axis: usize,
+        slice: impl Into<Slice>,
+    ) -> ArrayViewBase<'_, Strided<N>, B>
+    where
+        L: Clone + Into<Strided<N>>,
+    {
+        let mut layout: Strided<N> = self.layout.clone().into();
+        let offset = layout.slice_axis(axis, slice.into());
+        ArrayViewBase {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout,
+                storage: unsafe { self.storage.offset(offset) },
+                _dst: [],
+            }),
+            life: PhantomData,
+        }
+    }
+
+    /// Return a mutable view of the array with `axis` restricted to `slice`.
+    ///
+    /// See [`slice_axis`](Self::slice_axis).
+    pub fn slice_axis_mut<const N: usize>(
+        &mut self,
+        axis: usize,
+        slice: impl Into<Slice>,
+    ) -> ArrayViewBaseMut<'_, Strided<N>, B>
+    where
+        L: Clone + Into<Strided<N>>,
+    {
+        let mut layout: Strided<N> = self.layout.clone().into();
+        let offset = layout.slice_axis(axis, slice.into());
+        ArrayViewBaseMut {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout,
+                storage: unsafe { self.storage.offset(offset) },
+                _dst: [],
+            }),
+            life: PhantomData,
+        }
+    }
+
+    /// Return a view of the array broadcast to `shape`, following NumPy's rules.
+    ///
+    /// Axes of length one, and any leading axes the array lacks, repeat their elements
+    /// with a stride of zero, so nothing is copied. For example, a row of length 3 can
+    /// be viewed as a `[4, 3]` array of four identical rows. See [`Broadcast`].
+    ///
+    /// Fails if an axis has neither its length in `shape` nor length one.
+    pub fn broadcast<const N: usize, const M: usize>(
+        &self,
+        shape: [usize; M],
+    ) -> Result<ArrayViewBase<'_, Strided<M>, B>, ShapeError>
+    where
+        L: Clone + Into<Strided<N>>,
+        Strided<N>: Broadcast<M>,
+    {
+        let layout: Strided<N> = self.layout.clone().into();
+        Ok(ArrayViewBase {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout: layout.broadcast(shape)?,
+                storage: unsafe { self.storage.offset(0) },
+                _dst: [],
+            }),
+            life: PhantomData,
+        })
+    }
+
+    /// Return a view of the elements at `index` along `axis`, with that axis removed.
+    ///
+    /// `axis` may be a position or, for [`Named`](crate::core::Named) layouts, a name.
+    /// For example, `volume.index_axis("time", t)` views time-slice `t` of a 3-D volume
+    /// as a 2-D array. The view keeps the layout's own type where it can, including the
+    /// names of the remaining axes; see [`IndexAxis`].
+    ///
+    /// **Panics** if `axis` selects no axis, or if `index` is out of bounds.
+    pub fn index_axis(
+        &self,
+        axis: impl AxisSelector<L>,
+        index: usize,
+    ) -> ArrayViewBase<'_, L::Smaller, B>
+    where
+        L: IndexAxis,
+    {
+        ArrayViewBase {
+            aref: ArrayRefBase(self.raw_index_axis(axis, index)),
+            life: PhantomData,
+        }
+    }
+
+    /// Return a mutable view of the elements at `index` along `axis`, with that axis removed.
+    ///
+    /// See [`index_axis`](Self::index_axis).
+    pub fn index_axis_mut(
+        &mut self,
+        axis: impl AxisSelector<L>,
+        index: usize,
+    ) -> ArrayViewBaseMut<'_, L::Smaller, B>
+    where
+        L: IndexAxis,
+    {
+        ArrayViewBaseMut {
+            aref: ArrayRefBase(self.raw_index_axis(axis, index)),
+            life: PhantomData,
+        }
+    }
+
+    /// Return a view of the array with a new axis of length one before `axis`,
+    /// or after the last axis if `axis` is the number of axes.
+    ///
+    /// The view keeps the layout's own type where it can; see [`InsertAxis`].
+    ///
+    /// **Panics** if `axis` is greater than the number of axes.
+    pub fn insert_axis(&self, axis: usize) -> ArrayViewBase<'_, L::Larger, B>
+    where
+        L: InsertAxis,
+    {
+        ArrayViewBase {
+            aref: ArrayRefBase(self.raw_insert_axis(axis)),
+            life: PhantomData,
+        }
+    }
+
+    /// Return a mutable view of the array with a new axis of length one.
+    ///
+    /// See [`insert_axis`](Self::insert_axis).
+    pub fn insert_axis_mut(&mut self, axis: usize) -> ArrayViewBaseMut<'_, L::Larger, B>
+    where
+        L: InsertAxis,
+    {
+        ArrayViewBaseMut {
+            aref: ArrayRefBase(self.raw_insert_axis(axis)),
+            life: PhantomData,
+        }
+    }
+
+    /// Return a view of the array with its axes reordered, so that axis `i` of the view
+    /// is the axis `axes[i]` selects.
+    ///
+    /// Each axis may be picked out by position or, for [`Named`](crate::core::Named)
+    /// layouts, by name, as in `image.permuted_axes(&["x", "y"])`. The view keeps the
+    /// layout's own type where it can, including the axis names; see [`PermuteAxes`].
+    ///
+    /// **Panics** if any of `axes` selects no axis, or if they do not select each axis
+    /// exactly once.
+    pub fn permuted_axes<S: AxisSelector<L>>(&self, axes: &[S]) -> ArrayViewBase<'_, L::Permuted, B>
+    where
+        L: PermuteAxes,
+    {
+        ArrayViewBase {
+            aref: ArrayRefBase(self.raw_permuted_axes(axes)),
+            life: PhantomData,
+        }
+    }
+
+    /// Return a mutable view of the array with its axes reordered.
+    ///
+    /// See [`permuted_axes`](Self::permuted_axes).
+    pub fn permuted_axes_mut<S: AxisSelector<L>>(
+        &mut self,
+        axes: &[S],
+    ) -> ArrayViewBaseMut<'_, L::Permuted, B>
+    where
+        L: PermuteAxes,
+    {
+        ArrayViewBaseMut {
+            aref: ArrayRefBase(self.raw_permuted_axes(axes)),
+            life: PhantomData,
+        }
+    }
+
+    /// Return the sums of the elements along `axis`, in a new array with that axis removed.
+    ///
+    /// `axis` may be a position or, for [`Named`](crate::core::Named) layouts, a name,
+    /// and the result keeps the names of the remaining axes. Its elements are laid out
+    /// in row-major order, compacting a strided layout if need be; see
+    /// [`Layout::compacted`].
+    ///
+    /// **Panics** if `axis` selects no axis.
+    pub fn sum_axis(&self, axis: impl AxisSelector<L>) -> ArrayBase<L::Smaller, VecBackend<B::Elem>>
+    where
+        L: RemoveAxis,
+        B::Elem: Clone + Sum,
+    {
+        let axis = axis.select(&self.layout).unwrap_or_else(|e| panic!("{e}"));
+        let mut layout = self.layout.remove_axis(axis);
+        if !layout.is_standard_layout() {
+            layout = layout
+                .compacted()
+                .filter(|layout| layout.is_standard_layout())
+                .expect("removing an axis leaves a layout that can be made row-major");
+        }
+        let shape = self.layout.slice();
+        let mut sums = Vec::with_capacity(layout.size());
+        let mut index = vec![0; shape.len()];
+        let others_empty = shape
+            .iter()
+            .enumerate()
+            .any(|(i, &len)| i != axis && len == 0);
+        if !others_empty {
+            loop {
+                let sum = (0..shape[axis])
+                    .map(|i| {
+                        index[axis] = i;
+                        let offset = L::Indexer::with_slice(&index, |ix| self.layout.to_offset(ix));
+                        unsafe { self.storage.ref_from_offset(offset) }.clone()
+                    })
+                    .sum();
+                sums.push(sum);
+                // Step every other axis to the next index, stopping once they all wrap around
+                let wrapped = index
+                    .iter_mut()
+                    .zip(shape)
+                    .enumerate()
+                    .rev()
+                    .filter(|&(i, _)| i != axis)
+                    .all(|(_, (ix, &len))| {
+                        *ix += 1;
+                        if *ix == len {
+                            *ix = 0;
+                            true
+                        } else {
+                            false
+                        }
+                    });
+                if wrapped {
+                    break;
+                }
+            }
+        }
+        ArrayBase::from_shape_vec(layout, sums).expect("a row-major layout fits its own elements")
+    }
+
+    /// Return a reference to the first element in row-major order, or `None` if the
+    /// array is empty.
+    ///
+    /// This is the element at the first valid index, as [`get`](Self::get) would find it,
+    /// rather than whatever is stored first.
+    pub fn first(&self) -> Option<&B::Elem> {
+        self.first_offset()
+            .map(|offset| unsafe { self.storage.ref_from_offset(offset) })
+    }
+
+    /// Return a mutable reference to the first element in row-major order, or `None`
+    /// if the array is empty.
+    ///
+    /// See [`first`](Self::first).
+    pub fn first_mut(&mut self) -> Option<&mut B::Elem> {
+        self.first_offset()
+            .map(|offset| unsafe { self.storage.ref_mut_from_offset(offset) })
+    }
+
+    /// The offset of the first valid index in row-major order, or `None` if there is none.
+    fn first_offset(&self) -> Option<isize> {
+        // Every layout here accepts the all-zero index unless it is empty, so the search
+        // rarely goes any further.
+        let zeros = vec![0; self.layout.ndim()];
+        L::Indexer::with_slice(&zeros, |ix| {
+            self.layout
+                .contains_index(ix)
+                .then(|| self.layout.to_offset(ix))
+        })
+        .or_else(|| {
+            let mut first = None;
+            self.layout.for_each_offset(|offset| {
+                first.get_or_insert(offset);
+            });
+            first
+        })
+    }
+
+    /// Return a reference to the element at `index`, or `None` if it is not a valid index.
+    pub fn get<I: NdIndex<L>>(&self, index: I) -> Option<&B::Elem> {
+        index
+            .index_checked(&self.layout)
+            .map(|offset| unsafe { self.storage.ref_from_offset(offset) })
+    }
+
+    /// Return a mutable reference to the element at `index`, or `None` if it is not a valid index.
+    pub fn get_mut<I: NdIndex<L>>(&mut self, index: I) -> Option<&mut B::Elem> {
+        index
+            .index_checked(&self.layout)
+            .map(|offset| unsafe { self.storage.ref_mut_from_offset(offset) })
+    }
+
+    /// Return a reference to the element at `index`, without checking that it is valid.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `index` is a valid index into the array;
+    /// see [`Layout::contains_index`].
+    pub unsafe fn uget<I: NdIndex<L>>(&self, index: I) -> &B::Elem {
+        let offset = index.index_unchecked(&self.layout);
+        self.storage.ref_from_offset(offset)
+    }
+
+    /// Return a mutable reference to the element at `index`, without checking that it is valid.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `index` is a valid index into the array;
+    /// see [`Layout::contains_index`].
+    pub unsafe fn uget_mut<I: NdIndex<L>>(&mut self, index: I) -> &mut B::Elem {
+        let offset = index.index_unchecked(&self.layout);
+        self.storage.ref_mut_from_offset(offset)
+    }
+
+    /// Return the array's data as a slice, if its layout is contiguous and in standard order.
+    ///
+    /// See [`Layout::is_standard_layout`].
+    pub fn as_slice(&self) -> Option<&[B::Elem]> {
+        if self.layout.is_standard_layout() {
+            Some(unsafe { slice::from_raw_parts(self.as_ptr(), self.len()) })
+        } else {
+            None
+        }
+    }
+
+    /// Return the array's data as a mutable slice, if its layout is contiguous and in standard order.
+    ///
+    /// See [`Layout::is_standard_layout`].
+    pub fn as_slice_mut(&mut self) -> Option<&mut [B::Elem]> {
+        if self.layout.is_standard_layout() {
+            Some(unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len()) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<const N: usize, const T: usize, B: Backend> ArrayRefBase<Tiled<N, T>, B> {
+    /// Return a view of each tile of the array, in the order the tiles are stored.
+    ///
+    /// See [`Tiled::tiles`].
+    pub fn tiles(&self) -> impl Iterator<Item = ArrayViewBase<'_, Dense<N>, B>> {
+        self.layout.tiles().map(|tile| ArrayViewBase {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout: tile.layout,
+                storage: unsafe { self.storage.offset(tile.offset as isize) },
+                _dst: [],
+            }),
+            life: PhantomData,
+        })
+    }
+
+    /// Return a mutable view of each tile of the array, in the order the tiles are stored.
+    ///
+    /// See [`Tiled::tiles`].
+    pub fn tiles_mut(&mut self) -> impl Iterator<Item = ArrayViewBaseMut<'_, Dense<N>, B>> {
+        // The tiles do not overlap, so the views never alias one another.
+        let storage = &self.storage;
+        self.layout.tiles().map(move |tile| ArrayViewBaseMut {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout: tile.layout,
+                storage: unsafe { storage.offset(tile.offset as isize) },
+                _dst: [],
+            }),
+            life: PhantomData,
+        })
+    }
+}
+
+impl<B: Backend> ArrayRefBase<Banded, B> {
+    /// Return the elements of diagonal `k`, from the top left.
+    ///
+    /// See [`Banded::diagonal`].
+    pub fn diagonal(&self, k: isize) -> impl Iterator<Item = &B::Elem> {
+        self.layout
+            .diagonal(k)
+            .map(|(_, offset)| unsafe { self.storage.ref_from_offset(offset as isize) })
+    }
+
+    /// Return mutable references to the elements of diagonal `k`, from the top left.
+    ///
+    /// See [`Banded::diagonal`].
+    pub fn diagonal_mut(&mut self, k: isize) -> impl Iterator<Item = &mut B::Elem> {
+        // Each element of the diagonal has its own offset, so the references never alias.
+        let ptr = self.as_mut_ptr();
+        self.layout
+            .diagonal(k)
+            .map(move |(_, offset)| unsafe { &mut *ptr.add(offset) })
+    }
+
+    /// Compute the matrix-vector product `A x`, like LAPACK's `gbmv`, visiting only the band.
+    ///
+    /// **Panics** if `x` does not have one element per column of the matrix.
+    pub fn matvec(&self, x: &[B::Elem]) -> Vec<B::Elem>
+    where
+        B::Elem: Copy + Mul<Output = B::Elem> + Sum,
+    {
+        let [m, n] = *self.layout.shape();
+        assert_eq!(
+            x.len(),
+            n,
+            "vector has {} elements, but the matrix has {n} columns",
+            x.len()
+        );
+        let (kl, ku) = (self.layout.kl(), self.layout.ku());
+        (0..m)
+            .map(|i| {
+                (i.saturating_sub(kl)..n.min(i + ku + 1))
+                    .map(|j| {
+                        let offset = self.layout.to_offset(&[i, j]);
+                        *unsafe { self.storage.ref_from_offset(offset) } * x[j]
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+impl<A, const N: usize, B: Backend<Elem = A>> ArrayRefBase<Pitched<A, N>, B> {
+    /// Return each row of the array as a slice, skipping the padding between rows.
+    ///
+    /// Rows are visited in row-major order, and each one starts on an aligned boundary
+    /// if the array's data does, as checked constructors ensure.
+    pub fn rows(&self) -> impl Iterator<Item = &[B::Elem]> {
+        let len = self.layout.shape()[N - 1];
+        self.layout
+            .row_offsets()
+            .map(move |offset| unsafe { slice::from_raw_parts(self.as_ptr().add(offset), len) })
+    }
+
+    /// Return each row of the array as a mutable slice, skipping the padding between rows.
+    ///
+    /// See [`rows`](Self::rows).
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [B::Elem]> {
+        // Rows never overlap, so the slices never alias one another.
+        let len = self.layout.shape()[N - 1];
+        let ptr = self.as_mut_ptr();
+        self.layout
+            .row_offsets()
+            .map(move |offset| unsafe { slice::from_raw_parts_mut(ptr.add(offset), len) })
+    }
+}
+
+impl<L: Layout, B: Backend, I: NdIndex<L>> Index<I> for ArrayRefBase<L, B> {
+    type Output = B::Elem;
+
+    /// **Panics** if `index` is not a valid index into the array.
+    fn index(&self, index: I) -> &Self::Output {
+        self.get(index).unwrap_or_else(|| {
+            panic!(
+                "index is out of bounds for an array of shape {:?}",
+                self.layout.slice()
+            )
+        })
+    }
+}
+
+impl<L: Layout, B: Backend, I: NdIndex<L>> IndexMut<I> for ArrayRefBase<L, B> {
+    /// **Panics** if `index` is not a valid index into the array.
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        let offset = index.index_checked(&self.layout).unwrap_or_else(|| {
+            panic!(
+                "index is out of bounds for an array of shape {:?}",
+                self.layout.slice()
+            )
+        });
+        unsafe { self.storage.ref_mut_from_offset(offset) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{Array, Banded, ColMajor, Dense, Layout, Named, Strided};
+
+    fn named_cube() -> Array<i32, Named<Dense<3>>> {
+        let layout = Named::new(Dense::new([2, 3, 4]), ["a", "b", "c"]).unwrap();
+        Array::from_shape_vec(layout, (0..24).collect()).unwrap()
+    }
+
+    #[test]
+    fn permuted_axes_by_name_reads_the_same_elements() {
+        let array = named_cube();
+        let view = array.permuted_axes(&["c", "a", "b"]);
+        assert_eq!(view.layout().slice(), &[4, 2, 3]);
+        assert_eq!(
+            view.layout().names().collect::<Vec<_>>(),
+            [Some("c"), Some("a"), Some("b")]
+        );
+        for (k, i, j) in
+            (0..4).flat_map(|k| (0..2).flat_map(move |i| (0..3).map(move |j| (k, i, j))))
+        {
+            assert_eq!(view[[k, i, j]], array[[i, j, k]]);
+        }
+        assert_eq!(array.permuted_axes(&[2, 0, 1]).layout(), view.layout());
+    }
+
+    #[test]
+    fn permuted_axes_mut_writes_through() {
+        let mut array = named_cube();
+        array.permuted_axes_mut(&["c", "b", "a"])[[3, 1, 0]] = -1;
+        assert_eq!(array[[0, 1, 3]], -1);
+    }
+
+    #[test]
+    #[should_panic(expected = "z")]
+    fn permuted_axes_panics_on_an_unknown_name() {
+        named_cube().permuted_axes(&["c", "a", "z"]);
+    }
+
+    #[test]
+    fn sum_axis_by_position_and_by_name() {
+        let array = named_cube();
+        let by_name = array.sum_axis("b");
+        assert_eq!(by_name.layout().slice(), &[2, 4]);
+        assert_eq!(
+            by_name.layout().names().collect::<Vec<_>>(),
+            [Some("a"), Some("c")]
+        );
+        for (i, k) in (0..2).flat_map(|i| (0..4).map(move |k| (i, k))) {
+            let expected: i32 = (0..3).map(|j| array[[i, j, k]]).sum();
+            assert_eq!(by_name[[i, k]], expected);
+        }
+        assert_eq!(array.sum_axis(1).into_raw_vec(), by_name.into_raw_vec());
+    }
+
+    #[test]
+    fn sum_axis_of_a_strided_array_is_row_major() {
+        // Rows run backwards in memory, so the array is [[3, 4, 5], [0, 1, 2]]
+        let array = Array::from_shape_vec(Strided::new([2, 3], [-3, 1]), (0..6).collect()).unwrap();
+        let sums = array.sum_axis(0);
+        assert_eq!(sums.layout(), &Strided::new([3], [1]));
+        assert_eq!(sums.into_raw_vec(), [3, 5, 7]);
+        let transposed = array.permuted_axes(&[1, 0]);
+        let sums = transposed.sum_axis(0);
+        assert!(sums.layout().is_standard_layout());
+        assert_eq!(sums.into_raw_vec(), [12, 3]);
+    }
+
+    #[test]
+    fn sum_axis_of_edge_shapes() {
+        let array = Array::from_shape_vec(Dense::new([4]), vec![1, 2, 3, 4]).unwrap();
+        let total = array.sum_axis(0);
+        assert_eq!(total[[]], 10);
+        let empty = Array::<i32, _>::from_shape_vec(Dense::new([0, 3]), vec![]).unwrap();
+        assert_eq!(empty.sum_axis(0).into_raw_vec(), [0, 0, 0]);
+        assert!(empty.sum_axis(1).into_raw_vec().is_empty());
+    }
+
+    #[test]
+    fn first_is_the_element_at_the_first_index() {
+        let layout = Banded::new([4, 5], 1, 2);
+        let mut array = Array::from_shape_vec(layout, (0..20).collect()).unwrap();
+        assert_eq!(array.first(), Some(&2));
+        assert_eq!(array.first(), array.get([0, 0]));
+        *array.first_mut().unwrap() = -1;
+        assert_eq!(array[[0, 0]], -1);
+        let empty = Array::<i32, _>::from_shape_vec(Banded::new([3, 0], 1, 2), vec![]).unwrap();
+        assert_eq!(empty.first(), None);
+    }
+
+    #[test]
+    fn as_slice_needs_a_standard_layout() {
+        let mut array = Array::from_shape_vec(Dense::new([2, 3]), (0..6).collect()).unwrap();
+        assert_eq!(array.as_slice(), Some(&[0, 1, 2, 3, 4, 5][..]));
+        array.as_slice_mut().unwrap()[4] = -1;
+        assert_eq!(array[[1, 1]], -1);
+        assert_eq!(array.permuted_axes(&[1, 0]).as_slice(), None);
+
+        let mut array = Array::from_shape_vec(ColMajor::new([2, 3]), (0..6).collect()).unwrap();
+        assert_eq!(array.as_slice(), None);
+        assert_eq!(array.as_slice_mut(), None);
+        let array = Array::from_shape_vec(Strided::new([3], [-1]), (0..3).collect()).unwrap();
+        assert_eq!(array.as_slice(), None);
+    }
+
+    #[test]
+    fn banded_matvec_matches_a_dense_product() {
+        let layout = Banded::new([4, 5], 1, 2);
+        let array = Array::from_shape_vec(layout, (0..20).collect::<Vec<i64>>()).unwrap();
+        let x = [1, -2, 3, -4, 5];
+        let expected: Vec<i64> = (0..4)
+            .map(|i| {
+                (0..5)
+                    .map(|j| array.get([i, j]).copied().unwrap_or(0) * x[j])
+                    .sum()
+            })
+            .collect();
+        assert_eq!(array.matvec(&x), expected);
+    }
+
+    #[test]
+    fn banded_diagonals_read_and_write_the_band() {
+        let layout = Banded::new([4, 5], 1, 2);
+        let mut array = Array::from_shape_vec(layout, vec![0; layout.band_size()]).unwrap();
+        for (value, elem) in array.diagonal_mut(-1).enumerate() {
+            *elem = value + 1;
+        }
+        assert_eq!(array[[1, 0]], 1);
+        assert_eq!(array[[3, 2]], 3);
+        assert_eq!(array.diagonal(-1).copied().collect::<Vec<_>>(), [1, 2, 3]);
+        assert!(array.diagonal(0).all(|&elem| elem == 0));
+    }
 }
@@ -4,36 +4,58 @@
 //! define *references* to arrays. The point of this is that the references - not the arrays -
 //! will hold most of the behavior of a multidimensional array.
 
-use std::ops::{Deref, DerefMut};
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
 
-use crate::core::{storage::NonNullStorage, Backend};
+use crate::core::{Backend, VecBackend};
 
 /// A reference to an array whose elements may not be safe to dereference.
-#[derive(Debug)]
-pub struct RawArrayRefBase<L, B: Backend> {
+///
+/// The array types hold this with a `T` of `[(); 0]`, and dereference to it with a `T`
+/// of `[()]`, into which the former coerces. That makes the reference types unsized,
+/// so `mem::swap` cannot trade the layout and pointer of one array for another's while
+/// leaving their owners behind.
+pub struct RawArrayRefBase<L, B: Backend, T: ?Sized = [()]> {
     pub(crate) layout: L,
     pub(crate) storage: B::Ref,
+    pub(crate) _dst: T,
+}
+
+impl<L: fmt::Debug, B: Backend, T: ?Sized> fmt::Debug for RawArrayRefBase<L, B, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawArrayRefBase")
+            .field("layout", &self.layout)
+            .field("storage", &self.storage)
+            .finish()
+    }
 }
 
 /// A reference to an array whose elements are safe to dereference.
 #[derive(Debug)]
-pub struct ArrayRefBase<L, B: Backend>(pub(crate) RawArrayRefBase<L, B>);
+pub struct ArrayRefBase<L, B: Backend, T: ?Sized = [()]>(pub(crate) RawArrayRefBase<L, B, T>);
+
+pub type RawArrayRef<A, L> = RawArrayRefBase<L, VecBackend<A>>;
+pub type ArrayRef<A, L> = ArrayRefBase<L, VecBackend<A>>;
 
-pub type RawArrayRef<A, L> = RawArrayRefBase<L, NonNullStorage<A>>;
-pub type ArrayRef<A, L> = ArrayRefBase<L, NonNullStorage<A>>;
+/// The sized form of [`RawArrayRefBase`], which the array types hold.
+pub(crate) type RawArrayRefSized<L, B> = RawArrayRefBase<L, B, [(); 0]>;
+/// The sized form of [`ArrayRefBase`], which the array types hold.
+pub(crate) type ArrayRefSized<L, B> = ArrayRefBase<L, B, [(); 0]>;
 
-/// Now to link these two: I'm going to implement `Deref` and `DerefMut` from an ArrayRef
-/// to its inner `RawArrayRef`.
+// Now to link these two: I'm going to implement `Deref` and `DerefMut` from an ArrayRef
+// to its inner `RawArrayRef`.
 
-impl<L, B: Backend> Deref for ArrayRefBase<L, B> {
-    type Target = RawArrayRefBase<L, B>;
+impl<L, B: Backend, T: ?Sized> Deref for ArrayRefBase<L, B, T> {
+    type Target = RawArrayRefBase<L, B, T>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<L, B: Backend> DerefMut for ArrayRefBase<L, B> {
+impl<L, B: Backend, T: ?Sized> DerefMut for ArrayRefBase<L, B, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
@@ -1,5 +1,8 @@
+mod cow;
 mod derefs;
 mod impls;
+mod rank;
+mod stack;
 mod structs;
 
 pub use structs::*;
@@ -0,0 +1,145 @@
+//! ## Copy-on-Write Arrays
+//!
+//! Functions that usually don't need to copy their input, but sometimes do, can return a
+//! `CowArray`: a view when the input was fine as it was, and an owned array otherwise.
+//! Mutating one that is still a view copies it first.
+
+use std::marker::PhantomData;
+
+use crate::core::{
+    ownership::CowOwner, storage::NonNullStorage, ArrayRefBase, ArrayViewBase, Backend, CowBackend,
+    Layout, RawArrayRefBase, VecBackend,
+};
+
+use super::ArrayBase;
+
+impl<'a, L: Layout, A: Clone> ArrayBase<L, CowBackend<'a, A>> {
+    /// Returns `true` if the elements are borrowed from another array.
+    pub fn is_view(&self) -> bool {
+        matches!(self.own, CowOwner::View { .. })
+    }
+
+    /// Returns `true` if the elements are owned.
+    pub fn is_owned(&self) -> bool {
+        matches!(self.own, CowOwner::Owned(_))
+    }
+
+    /// Turn the array into an owned array, copying the elements only if they are borrowed.
+    pub fn into_owned(mut self) -> ArrayBase<L, VecBackend<A>> {
+        self.ensure_unique();
+        let own = match self.own {
+            CowOwner::Owned(own) => own,
+            CowOwner::View { .. } => unreachable!("a unique array owns its elements"),
+        };
+        ArrayBase {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout: self.aref.0.layout,
+                storage: self.aref.0.storage,
+                _dst: [],
+            }),
+            own,
+        }
+    }
+}
+
+impl<'a, L, A, B> From<ArrayViewBase<'a, L, B>> for ArrayBase<L, CowBackend<'a, A>>
+where
+    L: Layout,
+    A: Clone,
+    B: Backend<Elem = A, Ref = NonNullStorage<A>>,
+{
+    /// Borrow the elements of `view`, without copying them.
+    fn from(view: ArrayViewBase<'a, L, B>) -> Self {
+        let RawArrayRefBase {
+            layout, storage, ..
+        } = view.aref.0;
+        let extent = layout
+            .memory_extent()
+            .expect("the extent of an allocated array fits in an isize");
+        // SAFETY: the extent of a view lies within the allocation it borrows from.
+        let ptr = unsafe { storage.ptr.offset(extent.start) };
+        ArrayBase {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout,
+                storage,
+                _dst: [],
+            }),
+            own: CowOwner::View {
+                ptr,
+                len: extent.len(),
+                life: PhantomData,
+            },
+        }
+    }
+}
+
+impl<L, A: Clone> From<ArrayBase<L, VecBackend<A>>> for ArrayBase<L, CowBackend<'_, A>> {
+    /// Take ownership of the elements of `array`, without copying them.
+    fn from(array: ArrayBase<L, VecBackend<A>>) -> Self {
+        ArrayBase {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout: array.aref.0.layout,
+                storage: array.aref.0.storage,
+                _dst: [],
+            }),
+            own: CowOwner::Owned(array.own),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{Array, CowArray, Dense, Layout, Slice, Strided};
+
+    fn matrix(n: usize) -> Array<usize, Dense<2>> {
+        Array::from_shape_vec(Dense::new([n, n]), (0..n * n).collect()).unwrap()
+    }
+
+    #[test]
+    fn the_first_write_copies_and_leaves_the_source_alone() {
+        let source = matrix(3);
+        let mut cow = CowArray::from(source.index_axis(0, 1));
+        assert!(cow.is_view());
+        assert_eq!(cow[[2]], 5);
+        cow[[0]] = 100;
+        assert!(cow.is_owned());
+        assert_eq!(cow[[0]], 100);
+        assert_eq!(cow[[2]], 5);
+        assert_eq!(source[[1, 0]], 3);
+    }
+
+    #[test]
+    fn a_column_copies_only_its_own_elements() {
+        let source = matrix(4);
+        let mut cow = CowArray::from(source.index_axis(1, 2));
+        cow[[3]] += 100;
+        assert_eq!(cow.layout(), &Strided::new([4], [1]));
+        assert_eq!(cow.into_owned().into_raw_vec(), [2, 6, 10, 114]);
+        assert_eq!(source[[3, 2]], 14);
+    }
+
+    #[test]
+    fn a_contiguous_view_keeps_its_layout() {
+        let source = matrix(3);
+        let view = source.slice_axis::<2>(0, Slice::from(..).step_by(-1));
+        let layout = *view.layout();
+        let mut cow = CowArray::from(view);
+        cow[[0, 0]] = 100;
+        assert_eq!(cow.layout(), &layout);
+        assert_eq!(cow[[0, 0]], 100);
+        assert_eq!(cow[[2, 2]], 2);
+        assert_eq!(
+            cow.into_owned().into_raw_vec(),
+            [0, 1, 2, 3, 4, 5, 100, 7, 8]
+        );
+    }
+
+    #[test]
+    fn an_empty_view_copies_nothing() {
+        let source = matrix(3);
+        let mut cow = CowArray::from(source.slice_axis::<2>(1, 1..1));
+        assert!(cow.get_mut([0, 0]).is_none());
+        assert!(cow.is_owned());
+        assert_eq!(cow.layout().size(), 0);
+    }
+}
@@ -10,22 +10,31 @@
 use std::ops::{Deref, DerefMut};
 
 use crate::core::{
-    ArrayRefBase, ArrayViewBase, ArrayViewBaseMut, Backend, RawArrayRefBase, RawArrayViewBase,
-    RawArrayViewBaseMut,
+    ArrayRefBase, ArrayViewBase, ArrayViewBaseMut, Backend, Layout, RawArrayRefBase,
+    RawArrayViewBase, RawArrayViewBaseMut,
 };
 
 use super::ArrayBase;
 
+/// Every access to an owned array's elements comes through here, so this is where its
+/// storage is pointed back at elements that may have moved; see [`Backend::refresh`].
+///
+/// Once the array has been dereferenced, it is borrowed, so its elements stay put.
 impl<L, B: Backend> Deref for ArrayBase<L, B> {
     type Target = ArrayRefBase<L, B>;
 
     fn deref(&self) -> &Self::Target {
+        B::refresh(&self.own, &self.aref.0.storage);
         &self.aref
     }
 }
 
-impl<L, B: Backend> DerefMut for ArrayBase<L, B> {
+/// Every mutable access to an owned array's elements comes through here, so this is where
+/// a shared array is made the sole owner of its elements; see [`Backend::ensure_unique`].
+impl<L: Layout, B: Backend> DerefMut for ArrayBase<L, B> {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.ensure_unique();
+        B::refresh_mut(&mut self.own, &mut self.aref.0.storage);
         &mut self.aref
     }
 }
@@ -60,7 +69,7 @@ impl<L, B: Backend> Deref for RawArrayViewBase<L, B> {
     }
 }
 
-impl<'a, L, B: Backend> Deref for RawArrayViewBaseMut<L, B> {
+impl<L, B: Backend> Deref for RawArrayViewBaseMut<L, B> {
     type Target = RawArrayRefBase<L, B>;
 
     fn deref(&self) -> &Self::Target {
@@ -68,7 +77,7 @@ impl<'a, L, B: Backend> Deref for RawArrayViewBaseMut<L, B> {
     }
 }
 
-impl<'a, L, B: Backend> DerefMut for RawArrayViewBaseMut<L, B> {
+impl<L, B: Backend> DerefMut for RawArrayViewBaseMut<L, B> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.aref
     }
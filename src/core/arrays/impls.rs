@@ -1,9 +1,692 @@
-use crate::core::{Backend, Layout};
+use std::{marker::PhantomData, ptr::NonNull, rc::Rc, sync::Arc};
+
+use crate::core::{
+    backends::{aligned_owner, unshare},
+    ownership::{ArcOwner, RcOwner, VecOwner},
+    storage::NonNullStorage,
+    validate_alignment, validate_extent, validate_unaliased, ArcBackend, ArrayRefBase,
+    ArrayViewBase, ArrayViewBaseMut, AxisSelector, Backend, Dense, IndexAxis, InsertAxis, Layout,
+    PermuteAxes, RawArrayRefBase, RawArrayRefSized, RawArrayViewBase, RawArrayViewBaseMut,
+    RcBackend, ShapeError, Slice, Strided, VecBackend,
+};
 
 use super::ArrayBase;
 
 impl<L: Layout, B: Backend> ArrayBase<L, B> {
-    fn ensure_unique(&mut self) {
+    pub(super) fn ensure_unique(&mut self) {
         B::ensure_unique(self)
     }
 }
+
+// Going through `DerefMut` would copy shared elements, so methods that only change the
+// layout are repeated here to leave them shared.
+impl<const N: usize, B: Backend> ArrayBase<Strided<N>, B> {
+    /// Reverse the order of the elements along `axis`, without moving or copying any of them.
+    ///
+    /// See [`RawArrayRefBase::invert_axis`].
+    pub fn invert_axis(&mut self, axis: usize) {
+        let aref: &mut RawArrayRefBase<_, B> = &mut self.aref.0;
+        aref.invert_axis(axis)
+    }
+
+    /// Restrict `axis` to `slice`, without moving or copying any elements.
+    ///
+    /// See [`RawArrayRefBase::slice_axis_inplace`].
+    pub fn slice_axis_inplace(&mut self, axis: usize, slice: impl Into<Slice>) {
+        let aref: &mut RawArrayRefBase<_, B> = &mut self.aref.0;
+        aref.slice_axis_inplace(axis, slice)
+    }
+}
+
+impl<L: Layout, A> ArrayBase<L, VecBackend<A>> {
+    /// Create an array with the given layout over the elements of `v`.
+    ///
+    /// The layout may span fewer elements than `v` holds, but not more. If it places
+    /// elements before its origin, as negative strides do, the origin is moved along
+    /// so that the lowest of them is the first element of `v`. If the layout needs an
+    /// aligned origin, as a [`Pitched`](crate::core::Pitched) one does, the origin is
+    /// moved further along to the first aligned element that leaves room for the layout.
+    ///
+    /// Fails if the layout overflows, reaches past the end of `v`, maps two indices
+    /// to the same element without meaning to, or needs its origin to be aligned more
+    /// strictly than any of those elements are; see [`validate_unaliased`] and
+    /// [`validate_alignment`].
+    pub fn from_shape_vec(layout: L, v: Vec<A>) -> Result<Self, ShapeError> {
+        validate_unaliased(&layout)?;
+        let origin = validate_extent(&layout, v.len())?;
+        let origin = aligned_origin(&layout, v.as_ptr(), origin, v.len());
+        validate_alignment(&layout, v.as_ptr().wrapping_add(origin))?;
+        let mut own = VecOwner::from(v);
+        let storage = VecBackend::ref_from_owner_offset(&mut own, origin as isize);
+        Ok(ArrayBase {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout,
+                storage,
+                _dst: [],
+            }),
+            own,
+        })
+    }
+
+    /// Create an array with every element set to `elem`.
+    ///
+    /// The allocation leaves room to align the origin as the layout requires, so this is
+    /// how to create an owned array with a [`Pitched`](crate::core::Pitched) layout; any
+    /// padding is set to `elem` too.
+    ///
+    /// Fails if the layout overflows, maps two indices to the same element without meaning
+    /// to, or cannot be aligned over elements of type `A`.
+    pub fn from_elem(layout: L, elem: A) -> Result<Self, ShapeError>
+    where
+        A: Clone,
+    {
+        validate_unaliased(&layout)?;
+        let origin = validate_extent(&layout, usize::MAX)?;
+        let elems = vec![elem; extent_len(&layout)];
+        let (mut own, origin) = aligned_owner(&layout, elems, origin as isize)?;
+        let storage = VecBackend::ref_from_owner_offset(&mut own, origin);
+        Ok(ArrayBase {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout,
+                storage,
+                _dst: [],
+            }),
+            own,
+        })
+    }
+
+    /// Return the vector that holds the array's elements, in the order they are stored.
+    ///
+    /// Passing it back unchanged to [`from_shape_vec`](Self::from_shape_vec) with the same
+    /// layout gives back the same array: any padding in front of the elements is only as
+    /// long as it takes to align them.
+    pub fn into_raw_vec(self) -> Vec<A> {
+        self.own.into_vec()
+    }
+
+    /// Turn the array into one whose elements can be shared, without copying them.
+    pub fn into_shared(self) -> ArrayBase<L, ArcBackend<A>>
+    where
+        A: Clone,
+    {
+        self.into()
+    }
+}
+
+macro_rules! impl_shared_array {
+    ($backend:ident, $owner:ident, $rc:ident) => {
+        impl<L: Layout, A: Clone> ArrayBase<L, $backend<A>> {
+            /// Turn the array into an owned array, copying the elements only if they are
+            /// shared.
+            ///
+            /// A copy is compacted just as [`Backend::ensure_unique`] would.
+            pub fn into_owned(self) -> ArrayBase<L, VecBackend<A>> {
+                let RawArrayRefBase {
+                    layout, storage, ..
+                } = self.aref.0;
+                let (own, storage) = match $rc::try_unwrap(self.own.0) {
+                    Ok(own) => (own, storage),
+                    Err(shared) => unshare(&shared, &layout, &storage),
+                };
+                ArrayBase {
+                    aref: ArrayRefBase(RawArrayRefBase {
+                        layout,
+                        storage,
+                        _dst: [],
+                    }),
+                    own,
+                }
+            }
+        }
+
+        /// Shares the elements, which are only copied once one of the clones is mutated.
+        impl<L: Clone, A: Clone> Clone for ArrayBase<L, $backend<A>> {
+            fn clone(&self) -> Self {
+                ArrayBase {
+                    aref: ArrayRefBase(RawArrayRefBase {
+                        layout: self.aref.0.layout.clone(),
+                        storage: self.aref.0.storage,
+                        _dst: [],
+                    }),
+                    own: $owner($rc::clone(&self.own.0)),
+                }
+            }
+        }
+
+        impl<L, A: Clone> From<ArrayBase<L, VecBackend<A>>> for ArrayBase<L, $backend<A>> {
+            /// Share the elements of `array`, without copying them.
+            fn from(array: ArrayBase<L, VecBackend<A>>) -> Self {
+                ArrayBase {
+                    aref: ArrayRefBase(RawArrayRefBase {
+                        layout: array.aref.0.layout,
+                        storage: array.aref.0.storage,
+                        _dst: [],
+                    }),
+                    own: $owner($rc::new(array.own)),
+                }
+            }
+        }
+    };
+}
+
+impl_shared_array!(ArcBackend, ArcOwner, Arc);
+impl_shared_array!(RcBackend, RcOwner, Rc);
+
+impl<A> From<Vec<A>> for ArrayBase<Dense<1>, VecBackend<A>> {
+    /// Create a one-dimensional array of the elements of `v`.
+    fn from(v: Vec<A>) -> Self {
+        let layout = Dense::new([v.len()]);
+        let mut own = VecOwner::from(v);
+        let storage = VecBackend::ref_from_owner_offset(&mut own, 0);
+        ArrayBase {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout,
+                storage,
+                _dst: [],
+            }),
+            own,
+        }
+    }
+}
+
+/// Copies the elements the layout reaches into a new allocation, moving the origin along
+/// if it has to be aligned as the layout requires.
+impl<L: Layout + Clone, A: Clone> Clone for ArrayBase<L, VecBackend<A>> {
+    fn clone(&self) -> Self {
+        let layout = &self.aref.0.layout;
+        // SAFETY: the origin always lies within the array's own allocation.
+        let origin = unsafe { self.own.offset_of(self.aref.0.storage.ptr) };
+        // Padding before the extent is left behind, so it does not pile up with each copy
+        let extent = layout
+            .memory_extent()
+            .expect("the extent of an allocated array fits in an isize");
+        let elems = &self.own.as_slice()[(origin + extent.start) as usize..];
+        let (mut own, origin) =
+            aligned_owner(layout, elems[..extent.len()].to_vec(), -extent.start)
+                .expect("a copy of an aligned array can be aligned");
+        let storage = VecBackend::ref_from_owner_offset(&mut own, origin);
+        ArrayBase {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout: layout.clone(),
+                storage,
+                _dst: [],
+            }),
+            own,
+        }
+    }
+}
+
+impl<'a, L: Layout, A, B: Backend<Elem = A, Ref = NonNullStorage<A>>> ArrayViewBase<'a, L, B> {
+    /// Create a view with the given layout over the elements of `data`.
+    ///
+    /// The origin is placed as in [`ArrayBase::from_shape_vec`]. Since the view is
+    /// read-only, more than one index may map to the same element.
+    ///
+    /// Fails if the layout overflows, reaches past the end of `data`, or cannot be
+    /// aligned within it; see [`validate_alignment`].
+    pub fn from_shape(layout: L, data: &'a [A]) -> Result<Self, ShapeError> {
+        // SAFETY: a slice holds `len` elements, from the start of one allocation.
+        let aref = unsafe { place(layout, NonNull::from(data).cast(), data.len())? };
+        Ok(ArrayViewBase {
+            aref: ArrayRefBase(aref),
+            life: PhantomData,
+        })
+    }
+}
+
+impl<'a, L: Layout, A, B: Backend<Elem = A, Ref = NonNullStorage<A>>> ArrayViewBaseMut<'a, L, B> {
+    /// Create a mutable view with the given layout over the elements of `data`.
+    ///
+    /// The origin is placed as in [`ArrayBase::from_shape_vec`].
+    ///
+    /// Fails if the layout overflows, reaches past the end of `data`, cannot be aligned
+    /// within it, or maps two indices to the same element without meaning to; see
+    /// [`validate_alignment`] and [`validate_unaliased`].
+    pub fn from_shape(layout: L, data: &'a mut [A]) -> Result<Self, ShapeError> {
+        validate_unaliased(&layout)?;
+        let len = data.len();
+        // SAFETY: a slice holds `len` elements, from the start of one allocation.
+        let aref = unsafe { place(layout, NonNull::from(data).cast(), len)? };
+        Ok(ArrayViewBaseMut {
+            aref: ArrayRefBase(aref),
+            life: PhantomData,
+        })
+    }
+}
+
+impl<L: Layout, A, B: Backend<Elem = A, Ref = NonNullStorage<A>>> RawArrayViewBase<L, B> {
+    /// Create a raw view with the given layout over the `len` elements starting at `ptr`.
+    ///
+    /// The origin is placed as in [`ArrayBase::from_shape_vec`], and more than one
+    /// index may map to the same element.
+    ///
+    /// Fails if the layout overflows, needs more than `len` elements, or cannot be
+    /// aligned within them.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null, and the `len` elements starting at `ptr` must lie
+    /// within a single allocation.
+    pub unsafe fn from_shape_ptr(layout: L, ptr: *const A, len: usize) -> Result<Self, ShapeError> {
+        let aref = place(layout, NonNull::new_unchecked(ptr.cast_mut()), len)?;
+        Ok(RawArrayViewBase {
+            aref,
+            life: PhantomData,
+        })
+    }
+}
+
+impl<L: Layout, A, B: Backend<Elem = A, Ref = NonNullStorage<A>>> RawArrayViewBaseMut<L, B> {
+    /// Create a mutable raw view with the given layout over the `len` elements starting
+    /// at `ptr`.
+    ///
+    /// The origin is placed as in [`ArrayBase::from_shape_vec`], and more than one
+    /// index may map to the same element.
+    ///
+    /// Fails if the layout overflows, needs more than `len` elements, or cannot be
+    /// aligned within them.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null, and the `len` elements starting at `ptr` must lie
+    /// within a single allocation.
+    pub unsafe fn from_shape_ptr(layout: L, ptr: *mut A, len: usize) -> Result<Self, ShapeError> {
+        let aref = place(layout, NonNull::new_unchecked(ptr), len)?;
+        Ok(RawArrayViewBaseMut {
+            aref,
+            life: PhantomData,
+        })
+    }
+}
+
+macro_rules! impl_axis_views {
+    ($view:ident) => {
+        impl<'a, L: Layout, B: Backend> $view<'a, L, B> {
+            /// Turn the view into one of the elements at `index` along `axis`, with that
+            /// axis removed, which borrows from the same array.
+            ///
+            /// See [`ArrayRefBase::index_axis`].
+            pub fn index_axis_move(
+                self,
+                axis: impl AxisSelector<L>,
+                index: usize,
+            ) -> $view<'a, L::Smaller, B>
+            where
+                L: IndexAxis,
+            {
+                let aref: &ArrayRefBase<L, B> = &self.aref;
+                $view {
+                    aref: ArrayRefBase(aref.raw_index_axis(axis, index)),
+                    life: PhantomData,
+                }
+            }
+
+            /// Turn the view into one with its axes reordered, which borrows from the
+            /// same array.
+            ///
+            /// See [`ArrayRefBase::permuted_axes`].
+            pub fn permuted_axes_move<S: AxisSelector<L>>(
+                self,
+                axes: &[S],
+            ) -> $view<'a, L::Permuted, B>
+            where
+                L: PermuteAxes,
+            {
+                let aref: &ArrayRefBase<L, B> = &self.aref;
+                $view {
+                    aref: ArrayRefBase(aref.raw_permuted_axes(axes)),
+                    life: PhantomData,
+                }
+            }
+
+            /// Turn the view into one with a new axis of length one, which borrows from
+            /// the same array.
+            ///
+            /// See [`ArrayRefBase::insert_axis`].
+            pub fn insert_axis_move(self, axis: usize) -> $view<'a, L::Larger, B>
+            where
+                L: InsertAxis,
+            {
+                let aref: &ArrayRefBase<L, B> = &self.aref;
+                $view {
+                    aref: ArrayRefBase(aref.raw_insert_axis(axis)),
+                    life: PhantomData,
+                }
+            }
+        }
+    };
+}
+
+impl_axis_views!(ArrayViewBase);
+impl_axis_views!(ArrayViewBaseMut);
+
+/// The number of elements a validated layout reaches, counting from its lowest offset.
+pub(super) fn extent_len<L: Layout>(layout: &L) -> usize {
+    layout
+        .memory_extent()
+        .expect("the extent of a validated layout fits in an isize")
+        .len()
+}
+
+/// Return the first origin from `origin` on that is aligned as `layout` requires and
+/// leaves room for it within the `len` elements starting at `ptr`, or `origin` itself if
+/// there is none.
+fn aligned_origin<L: Layout, A>(layout: &L, ptr: *const A, origin: usize, len: usize) -> usize {
+    let room = len - extent_len(layout);
+    (origin..=origin + room)
+        .find(|&origin| layout.is_aligned_for(ptr.wrapping_add(origin)))
+        .unwrap_or(origin)
+}
+
+/// Place `layout` over the `len` elements starting at `ptr`, checking that it fits.
+///
+/// # Safety
+///
+/// The `len` elements starting at `ptr` must lie within a single allocation.
+unsafe fn place<L, A, B>(
+    layout: L,
+    ptr: NonNull<A>,
+    len: usize,
+) -> Result<RawArrayRefSized<L, B>, ShapeError>
+where
+    L: Layout,
+    B: Backend<Elem = A, Ref = NonNullStorage<A>>,
+{
+    let origin = validate_extent(&layout, len)?;
+    let origin = aligned_origin(&layout, ptr.as_ptr(), origin, len);
+    // The origin is at most `len`, so stays within the allocation or one past its end.
+    let storage = NonNullStorage {
+        ptr: ptr.add(origin),
+    };
+    validate_alignment(&layout, storage.ptr.as_ptr())?;
+    Ok(RawArrayRefBase {
+        layout,
+        storage,
+        _dst: [],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        ArcArray, Array, ArrayView, ArrayViewMut, CowArray, ErrorKind, Pitched, RawArrayView,
+        RcArray,
+    };
+
+    #[test]
+    fn from_shape_vec_rejects_a_layout_past_the_end() {
+        let err = Array::from_shape_vec(Dense::new([2, 3]), vec![0; 5])
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::OutOfBounds {
+                required: 6,
+                len: 5
+            }
+        );
+    }
+
+    #[test]
+    fn from_shape_vec_rejects_an_overflowing_layout() {
+        let err = Array::from_shape_vec(Dense::new([usize::MAX, 2]), vec![0; 4])
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), &ErrorKind::Overflow);
+    }
+
+    #[test]
+    fn from_shape_vec_rejects_aliased_elements() {
+        let err = Array::from_shape_vec(Strided::new([2, 3], [0, 1]), vec![0; 3])
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), &ErrorKind::AliasedElements);
+    }
+
+    #[test]
+    fn from_shape_vec_places_the_origin_after_negative_strides() {
+        let array = Array::from_shape_vec(Strided::new([2, 3], [-3, 1]), (0..6).collect()).unwrap();
+        assert_eq!(array[[0, 0]], 3);
+        assert_eq!(array[[1, 2]], 2);
+    }
+
+    #[test]
+    fn negative_strides_survive_the_round_trip_through_into_raw_vec() {
+        let layout = Strided::new([2, 3], [-1, -2]);
+        let array = Array::from_shape_vec(layout, (0..6).collect()).unwrap();
+        assert_eq!(array[[0, 0]], 5);
+        assert_eq!(array[[1, 2]], 0);
+        let raw = array.into_raw_vec();
+        assert_eq!(raw, [0, 1, 2, 3, 4, 5]);
+        let array = Array::from_shape_vec(layout, raw).unwrap();
+        assert_eq!(array[[0, 1]], 3);
+    }
+
+    #[test]
+    fn arrays_read_through_inverted_and_reversed_axes() {
+        let mut array =
+            Array::from_shape_vec(Strided::new([3, 4], [4, 1]), (0..12).collect()).unwrap();
+        array.invert_axis(0);
+        assert_eq!(array[[0, 0]], 8);
+        assert_eq!(array[[2, 3]], 3);
+        array.slice_axis_inplace(1, Slice::new(1, Some(-1), -1));
+        assert_eq!(array.layout(), &Strided::new([3, 2], [-4, -1]));
+        let rows: Vec<Vec<i32>> = (0..3)
+            .map(|i| (0..2).map(|j| array[[i, j]]).collect())
+            .collect();
+        assert_eq!(rows, [[10, 9], [6, 5], [2, 1]]);
+        array[[2, 1]] = -1;
+        assert_eq!(array.into_raw_vec()[1], -1);
+    }
+
+    #[test]
+    fn clones_of_owned_arrays_are_independent() {
+        let array =
+            Array::from_shape_vec(Strided::new([2, 3], [-3, -1]), (0..6).collect()).unwrap();
+        let mut copy = array.clone();
+        assert_eq!(copy.layout(), array.layout());
+        copy[[0, 0]] = -1;
+        assert_eq!(array[[0, 0]], 5);
+        assert_eq!(copy.into_raw_vec(), [0, 1, 2, 3, 4, -1]);
+        assert_eq!(array.into_raw_vec(), [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn owned_arrays_drop_each_element_once() {
+        let elem = Rc::new(());
+        let array =
+            Array::from_shape_vec(Strided::new([2, 2], [-2, 1]), vec![elem.clone(); 5]).unwrap();
+        // A copy leaves behind the element the layout does not reach
+        let copy = array.clone();
+        assert_eq!(Rc::strong_count(&elem), 10);
+        drop(array);
+        assert_eq!(Rc::strong_count(&elem), 5);
+        assert_eq!(copy.into_raw_vec().len(), 4);
+        assert_eq!(Rc::strong_count(&elem), 1);
+    }
+
+    fn shared_square() -> ArcArray<i32, Strided<2>> {
+        Array::from_shape_vec(Strided::new([4, 4], [4, 1]), (0..16).collect())
+            .unwrap()
+            .into_shared()
+    }
+
+    #[test]
+    fn writes_to_a_shared_clone_leave_the_original_alone() {
+        let array = shared_square();
+        let mut copy = array.clone();
+        copy[[1, 2]] = -1;
+        assert_eq!(copy[[1, 2]], -1);
+        assert_eq!(array[[1, 2]], 6);
+        assert_eq!(
+            array.into_owned().into_raw_vec(),
+            (0..16).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn shared_slices_are_compacted_on_write() {
+        let array = shared_square();
+        let mut row = array.clone();
+        row.slice_axis_inplace(0, 2..3);
+        row.invert_axis(1);
+        let layout = *row.layout();
+        row[[0, 3]] = -1;
+        assert_eq!(row.layout(), &layout);
+        assert_eq!(
+            (0..4).map(|j| row[[0, j]]).collect::<Vec<_>>(),
+            [11, 10, 9, -1]
+        );
+        assert_eq!(row.into_owned().into_raw_vec(), [-1, 9, 10, 11]);
+        assert_eq!(array[[2, 0]], 8);
+    }
+
+    #[test]
+    fn writes_to_an_rc_clone_leave_the_original_alone() {
+        let array: RcArray<i32, _> =
+            Array::from_shape_vec(Strided::new([2, 3], [3, 1]), (0..6).collect())
+                .unwrap()
+                .into();
+        let mut copy = array.clone();
+        copy.invert_axis(0);
+        copy[[0, 0]] = -1;
+        assert_eq!(copy[[1, 0]], 0);
+        assert_eq!(array[[1, 0]], 3);
+        assert_eq!(copy.into_owned().into_raw_vec(), [0, 1, 2, -1, 4, 5]);
+        assert_eq!(
+            array.into_owned().into_raw_vec(),
+            (0..6).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn views_accept_aliased_elements_unless_mutable() {
+        let mut data = [1, 2, 3];
+        let view = ArrayView::from_shape(Strided::new([2, 3], [0, 1]), &data).unwrap();
+        assert_eq!(view[[1, 2]], 3);
+        let err = ArrayViewMut::from_shape(Strided::new([2, 3], [0, 1]), &mut data)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), &ErrorKind::AliasedElements);
+    }
+
+    #[test]
+    fn raw_views_reject_a_layout_past_the_end() {
+        let data = [0; 5];
+        // SAFETY: the five elements lie within `data`.
+        let err = unsafe { RawArrayView::from_shape_ptr(Dense::new([2, 3]), data.as_ptr(), 5) }
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::OutOfBounds {
+                required: 6,
+                len: 5
+            }
+        );
+    }
+
+    fn is_aligned(row: &[f32]) -> bool {
+        (row.as_ptr() as usize).is_multiple_of(32)
+    }
+
+    fn pitched() -> Array<f32, Pitched<f32, 2>> {
+        let mut array = Array::from_elem(Pitched::new([3, 5], 32), 0.0).unwrap();
+        for (i, row) in array.rows_mut().enumerate() {
+            for (j, elem) in row.iter_mut().enumerate() {
+                *elem = (i * 10 + j) as f32;
+            }
+        }
+        array
+    }
+
+    #[test]
+    fn from_elem_aligns_every_row() {
+        let array = pitched();
+        assert!(array.rows().all(is_aligned));
+        assert_eq!(array.rows().nth(1).unwrap(), [10.0, 11.0, 12.0, 13.0, 14.0]);
+        assert_eq!(array[[2, 4]], 24.0);
+    }
+
+    #[test]
+    fn from_elem_rejects_elements_of_the_wrong_size() {
+        assert!(Array::from_elem(Pitched::<f32, 2>::new([3, 5], 32), 0.0_f64).is_err());
+    }
+
+    #[test]
+    fn copies_stay_aligned() {
+        let array = pitched();
+        for _ in 0..8 {
+            let copy = array.clone();
+            assert!(copy.rows().all(is_aligned));
+            assert!(copy.rows().eq(array.rows()));
+            let mut shared = array.clone().into_shared();
+            let other = shared.clone();
+            shared[[0, 0]] = -1.0;
+            assert!(shared.rows().all(is_aligned));
+            assert_eq!(other[[0, 0]], 0.0);
+        }
+    }
+
+    /// Floats that start on a 32-byte boundary, wherever the buffer is.
+    #[repr(align(32))]
+    struct AlignedFloats([f32; 40]);
+
+    fn aligned_floats() -> AlignedFloats {
+        AlignedFloats(std::array::from_fn(|i| i as f32))
+    }
+
+    #[test]
+    fn copies_of_copies_round_trip_through_into_raw_vec() {
+        let mut array = pitched();
+        for _ in 0..8 {
+            array = array.clone();
+        }
+        let raw = array.into_raw_vec();
+        // At most the padding it takes to align the copy, then the 21 floats of the extent
+        assert!(raw.len() < 8 + 21, "{}", raw.len());
+        let array = Array::from_shape_vec(Pitched::new([3, 5], 32), raw).unwrap();
+        assert!(array.rows().all(is_aligned));
+        assert!(array.rows().eq(pitched().rows()));
+    }
+
+    #[test]
+    fn borrowed_copies_stay_aligned() {
+        let data = aligned_floats();
+        let view = ArrayView::from_shape(Pitched::new([3, 5], 32), &data.0).unwrap();
+        let mut cow = CowArray::from(view);
+        cow[[1, 1]] = -1.0;
+        assert!(cow.rows().all(is_aligned));
+        assert_eq!(cow[[1, 1]], -1.0);
+        assert_eq!(cow[[2, 4]], 20.0);
+        assert_eq!(data.0[9], 9.0);
+    }
+
+    #[test]
+    fn views_move_the_origin_to_an_aligned_element() {
+        let mut data = aligned_floats();
+        let layout = Pitched::new([3, 5], 32);
+        let view = ArrayView::from_shape(layout, &data.0[1..]).unwrap();
+        assert!(view.rows().all(is_aligned));
+        assert_eq!(view[[0, 0]], 8.0);
+        let view = ArrayViewMut::from_shape(layout, &mut data.0[1..]).unwrap();
+        assert_eq!(view[[2, 4]], 28.0);
+        // With no room to move along, the origin stays where it is
+        let err = ArrayView::from_shape(layout, &data.0[1..25]).err().unwrap();
+        assert_eq!(err.kind(), &ErrorKind::Misaligned);
+    }
+
+    #[test]
+    fn from_shape_vec_moves_the_origin_to_an_aligned_element() {
+        let array = pitched();
+        let raw = array.into_raw_vec();
+        let array = Array::from_shape_vec(Pitched::new([3, 5], 32), raw).unwrap();
+        assert!(array.rows().all(is_aligned));
+        assert_eq!(array[[1, 2]], 12.0);
+        // However a vector is aligned, one of its first eight floats is on a 32-byte boundary
+        let array = Array::from_shape_vec(Pitched::new([3, 5], 32), vec![1.0_f32; 31]).unwrap();
+        assert!(array.rows().all(is_aligned));
+        assert_eq!(array[[2, 4]], 1.0);
+    }
+}
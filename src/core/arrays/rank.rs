@@ -0,0 +1,133 @@
+//! ## Rank Conversions
+//!
+//! Library boundaries tend to be dynamic-rank, while hot loops want a fixed rank.
+//! So every array type can swap its layout for one of a different rank, as long as the
+//! layouts themselves convert: a `Dense<N>` always becomes a `DenseDyn`, and a `DenseDyn`
+//! becomes a `Dense<N>` if it has exactly `N` axes. The elements never move.
+
+use std::convert::Infallible;
+
+use crate::core::{
+    ArrayRefBase, ArrayRefSized, ArrayViewBase, ArrayViewBaseMut, Backend, Dense, DenseDyn, Layout,
+    RawArrayRefBase, RawArrayRefSized, RawArrayViewBase, RawArrayViewBaseMut, ShapeError,
+};
+
+use super::ArrayBase;
+
+impl<L, B: Backend> RawArrayRefSized<L, B> {
+    fn try_map_layout<L2, E>(
+        self,
+        f: impl FnOnce(L) -> Result<L2, E>,
+    ) -> Result<RawArrayRefSized<L2, B>, E> {
+        Ok(RawArrayRefBase {
+            layout: f(self.layout)?,
+            storage: self.storage,
+            _dst: [],
+        })
+    }
+}
+
+impl<L, B: Backend> ArrayRefSized<L, B> {
+    fn try_map_layout<L2, E>(
+        self,
+        f: impl FnOnce(L) -> Result<L2, E>,
+    ) -> Result<ArrayRefSized<L2, B>, E> {
+        self.0.try_map_layout(f).map(ArrayRefBase)
+    }
+}
+
+macro_rules! impl_rank_conversions {
+    ($array:ident, [$($lt:lifetime)?], $field:ident) => {
+        impl<$($lt,)? L: Layout, B: Backend> $array<$($lt,)? L, B> {
+            /// Convert into an array of dynamic rank.
+            pub fn into_dyn(self) -> $array<$($lt,)? DenseDyn, B>
+            where
+                L: Into<DenseDyn>,
+            {
+                let Ok(array) = self.try_map_layout(|layout| Ok::<_, Infallible>(layout.into()));
+                array
+            }
+
+            /// Convert into an array with layout `L2`, such as a fixed-rank `Dense<N>`.
+            ///
+            /// Fails if the layout does not convert, as when a `DenseDyn` does not have
+            /// the rank of the fixed-rank layout it is converted into.
+            pub fn into_dimensionality<L2>(self) -> Result<$array<$($lt,)? L2, B>, ShapeError>
+            where
+                L2: Layout + TryFrom<L>,
+                ShapeError: From<<L2 as TryFrom<L>>::Error>,
+            {
+                self.try_map_layout(|layout| L2::try_from(layout).map_err(ShapeError::from))
+            }
+
+            fn try_map_layout<L2, E>(
+                self,
+                f: impl FnOnce(L) -> Result<L2, E>,
+            ) -> Result<$array<$($lt,)? L2, B>, E> {
+                Ok($array {
+                    aref: self.aref.try_map_layout(f)?,
+                    $field: self.$field,
+                })
+            }
+        }
+
+        impl<$($lt,)? const N: usize, B: Backend> From<$array<$($lt,)? Dense<N>, B>>
+            for $array<$($lt,)? DenseDyn, B>
+        {
+            fn from(value: $array<$($lt,)? Dense<N>, B>) -> Self {
+                value.into_dyn()
+            }
+        }
+
+        impl<$($lt,)? const N: usize, B: Backend> TryFrom<$array<$($lt,)? DenseDyn, B>>
+            for $array<$($lt,)? Dense<N>, B>
+        {
+            type Error = ShapeError;
+
+            /// Fails if `value` does not have exactly `N` dimensions.
+            fn try_from(value: $array<$($lt,)? DenseDyn, B>) -> Result<Self, Self::Error> {
+                value.into_dimensionality()
+            }
+        }
+    };
+}
+
+impl_rank_conversions!(ArrayBase, [], own);
+impl_rank_conversions!(ArrayViewBase, ['a], life);
+impl_rank_conversions!(ArrayViewBaseMut, ['a], life);
+impl_rank_conversions!(RawArrayViewBase, [], life);
+impl_rank_conversions!(RawArrayViewBaseMut, [], life);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Array, ArrayView, ErrorKind};
+
+    #[test]
+    fn rank_conversions_keep_the_elements_in_place() {
+        let array = Array::from_shape_vec(Dense::new([2, 3]), (0..6).collect()).unwrap();
+        let ptr = array.as_ptr();
+        let array = array.into_dyn();
+        assert_eq!(array.layout(), &DenseDyn::new(&[2, 3]));
+        assert_eq!(array[[1, 2].as_ref()], 5);
+        let array = array.into_dimensionality::<Dense<2>>().unwrap();
+        assert_eq!(array.as_ptr(), ptr);
+        assert_eq!(array[[1, 0]], 3);
+    }
+
+    #[test]
+    fn into_dimensionality_needs_a_matching_rank() {
+        let data = [0; 6];
+        let view = ArrayView::from_shape(DenseDyn::new(&[2, 3]), &data).unwrap();
+        let err = view.into_dimensionality::<Dense<3>>().err().unwrap();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::IncompatibleRank {
+                expected: 3,
+                found: 2
+            }
+        );
+        let view = ArrayView::from_shape(DenseDyn::new(&[6]), &data).unwrap();
+        assert!(ArrayView::<_, Dense<2>>::try_from(view).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! ## Stack Arrays
+//!
+//! Where the heap is off limits, a `StackArray` keeps its elements inline, in a buffer with
+//! room for `CAP` of them. Creating, moving, or cloning one never allocates; a layout that
+//! needs more than `CAP` elements is refused when the array is created.
+
+use std::ptr::NonNull;
+
+use crate::core::{
+    ownership::StackOwner, validate_alignment, validate_extent, validate_unaliased, ArrayRefBase,
+    Backend, Layout, RawArrayRefBase, ShapeError, StackBackend,
+};
+
+use super::{impls::extent_len, ArrayBase};
+
+impl<L: Layout, A, const CAP: usize> ArrayBase<L, StackBackend<A, CAP>> {
+    /// Create an array by copying its elements from `data`, in the order they are stored.
+    ///
+    /// Only as many elements as the layout reaches are copied; any after those are ignored.
+    ///
+    /// Fails if the layout overflows, maps two indices to the same element without meaning
+    /// to, reaches past the end of `data` or beyond `CAP` elements, or needs its rows
+    /// aligned more strictly than `A` is. The buffer moves with the array, so it is never
+    /// aligned any further.
+    pub fn from_shape_slice(layout: L, data: &[A]) -> Result<Self, ShapeError>
+    where
+        A: Clone,
+    {
+        validate_unaliased(&layout)?;
+        let origin = validate_extent(&layout, data.len().min(CAP))?;
+        validate_inline_alignment::<L, A>(&layout, origin)?;
+        let len = extent_len(&layout);
+        Ok(Self::place(
+            layout,
+            StackOwner::from_slice(&data[..len]),
+            origin,
+        ))
+    }
+
+    /// Create an array with every element set to `elem`.
+    ///
+    /// Fails if the layout overflows, maps two indices to the same element without meaning
+    /// to, reaches beyond `CAP` elements, or needs its rows aligned more strictly than `A`
+    /// is; see [`from_shape_slice`](Self::from_shape_slice).
+    pub fn from_elem(layout: L, elem: A) -> Result<Self, ShapeError>
+    where
+        A: Clone,
+    {
+        validate_unaliased(&layout)?;
+        let origin = validate_extent(&layout, CAP)?;
+        validate_inline_alignment::<L, A>(&layout, origin)?;
+        let len = extent_len(&layout);
+        Ok(Self::place(
+            layout,
+            StackOwner::from_fn(len, |_| elem.clone()),
+            origin,
+        ))
+    }
+}
+
+impl<L, A, const CAP: usize> ArrayBase<L, StackBackend<A, CAP>> {
+    fn place(layout: L, mut own: StackOwner<A, CAP>, origin: usize) -> Self {
+        let storage = StackBackend::ref_from_owner_offset(&mut own, origin as isize);
+        ArrayBase {
+            aref: ArrayRefBase(RawArrayRefBase {
+                layout,
+                storage,
+                _dst: [],
+            }),
+            own,
+        }
+    }
+}
+
+/// Copies the elements into a new inline buffer, keeping the layout and origin.
+impl<L: Clone, A: Clone, const CAP: usize> Clone for ArrayBase<L, StackBackend<A, CAP>> {
+    fn clone(&self) -> Self {
+        let layout = self.aref.0.layout.clone();
+        Self::place(
+            layout,
+            self.own.clone(),
+            self.aref.0.storage.origin as usize,
+        )
+    }
+}
+
+/// Check the alignment of `layout` as if the inline buffer sat at the least aligned
+/// address it could.
+///
+/// The buffer moves with the array, so it is only ever aligned for `A`; a layout that
+/// needs its rows aligned more strictly than that is refused.
+fn validate_inline_alignment<L: Layout, A>(layout: &L, origin: usize) -> Result<(), ShapeError> {
+    validate_alignment(
+        layout,
+        NonNull::<A>::dangling().as_ptr().wrapping_add(origin),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{ErrorKind, StackArray, Strided};
+
+    // Built in a frame of its own, so the buffer moves when it is returned
+    fn reversed_rows() -> StackArray<i32, Strided<2>, 8> {
+        StackArray::from_shape_slice(Strided::new([2, 3], [-3, 1]), &[0, 1, 2, 3, 4, 5]).unwrap()
+    }
+
+    #[test]
+    fn moved_and_cloned_arrays_read_their_own_buffer() {
+        let array = reversed_rows();
+        let moved = Box::new(array);
+        assert_eq!(moved[[0, 0]], 3);
+        let mut copy = (*moved).clone();
+        copy[[0, 0]] = -1;
+        assert_eq!(copy[[0, 0]], -1);
+        assert_eq!(copy[[1, 2]], 2);
+        assert_eq!(moved[[0, 0]], 3);
+    }
+
+    #[test]
+    fn layouts_beyond_the_capacity_are_refused() {
+        let err = StackArray::<i32, _, 5>::from_elem(Strided::new([2, 3], [3, 1]), 0)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::OutOfBounds {
+                required: 6,
+                len: 5
+            }
+        );
+        let err = StackArray::<i32, _, 8>::from_shape_slice(Strided::new([2, 3], [3, 1]), &[0; 4])
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::OutOfBounds {
+                required: 6,
+                len: 4
+            }
+        );
+        assert!(StackArray::<i32, _, 6>::from_elem(Strided::new([2, 3], [3, 1]), 0).is_ok());
+    }
+}
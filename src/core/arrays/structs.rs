@@ -14,7 +14,8 @@
 //! See [`crate::array_deref`] for how this is accomplished.
 
 use crate::core::{
-    storage::NonNullStorage, ArcBackend, ArrayRefBase, Backend, RawArrayRefBase, VecBackend,
+    ArcBackend, ArrayRefSized, Backend, CowBackend, RawArrayRefSized, RcBackend, StackBackend,
+    VecBackend,
 };
 use std::marker::PhantomData;
 
@@ -27,37 +28,48 @@ use std::marker::PhantomData;
 ///     3. `O`, the ownership, representing any additional information needed for memory management.
 #[derive(Debug)]
 pub struct ArrayBase<L, B: Backend> {
-    pub(crate) aref: ArrayRefBase<L, B>,
+    pub(crate) aref: ArrayRefSized<L, B>,
     pub(crate) own: B::Owned,
 }
 
 pub type Array<A, L> = ArrayBase<L, VecBackend<A>>;
 pub type ArcArray<A, L> = ArrayBase<L, ArcBackend<A>>;
+pub type RcArray<A, L> = ArrayBase<L, RcBackend<A>>;
+pub type CowArray<'a, A, L> = ArrayBase<L, CowBackend<'a, A>>;
+pub type StackArray<A, L, const CAP: usize> = ArrayBase<L, StackBackend<A, CAP>>;
 
 /// A view of an existing array.
 #[derive(Debug)]
 pub struct ArrayViewBase<'a, L, B: Backend> {
-    pub(crate) aref: ArrayRefBase<L, B>,
+    pub(crate) aref: ArrayRefSized<L, B>,
     pub(crate) life: PhantomData<&'a B::Elem>,
 }
 
+pub type ArrayView<'a, A, L> = ArrayViewBase<'a, L, VecBackend<A>>;
+
 /// A mutable view of an existing array
 #[derive(Debug)]
 pub struct ArrayViewBaseMut<'a, L, B: Backend> {
-    pub(crate) aref: ArrayRefBase<L, B>,
+    pub(crate) aref: ArrayRefSized<L, B>,
     pub(crate) life: PhantomData<&'a mut B::Elem>,
 }
 
+pub type ArrayViewMut<'a, A, L> = ArrayViewBaseMut<'a, L, VecBackend<A>>;
+
 /// A view of an array without a lifetime, and whose elements are not safe to dereference.
 #[derive(Debug)]
 pub struct RawArrayViewBase<L, B: Backend> {
-    pub(crate) aref: RawArrayRefBase<L, B>,
+    pub(crate) aref: RawArrayRefSized<L, B>,
     pub(crate) life: PhantomData<*const B::Elem>,
 }
 
+pub type RawArrayView<A, L> = RawArrayViewBase<L, VecBackend<A>>;
+
 /// A mutable view of an array without a lifetime, and whose elements are not safe to dereference.
 #[derive(Debug)]
 pub struct RawArrayViewBaseMut<L, B: Backend> {
-    pub(crate) aref: RawArrayRefBase<L, B>,
+    pub(crate) aref: RawArrayRefSized<L, B>,
     pub(crate) life: PhantomData<*mut B::Elem>,
 }
+
+pub type RawArrayViewMut<A, L> = RawArrayViewBaseMut<L, VecBackend<A>>;
@@ -1,83 +1,309 @@
-use core::fmt::Debug;
-use std::{marker::PhantomData, mem, ptr::NonNull, sync::Arc};
+use std::{iter, marker::PhantomData, mem, rc::Rc, sync::Arc};
 
 use super::{
-    ownership::{ArcOwner, VecOwner},
-    storage::NonNullStorage,
-    Backend,
+    ownership::{ArcOwner, CowOwner, RcOwner, StackOwner, VecOwner},
+    storage::{InlineStorage, NonNullStorage},
+    ArrayBase, Backend, ErrorKind, Layout, PointerStorage, RawArrayRefBase, ShapeError,
 };
 
 pub struct VecBackend<T> {
     phantom: PhantomData<T>,
 }
 
-unsafe impl<T: Debug> Backend for VecBackend<T> {
-    type Ref = NonNull<T>;
+unsafe impl<T> Backend for VecBackend<T> {
+    type Ref = NonNullStorage<T>;
 
     type Owned = VecOwner<T>;
 
     type Elem = T;
 
-    fn ensure_unique<L>(arr: &mut super::ArrayBase<L, Self>)
+    fn ensure_unique<L>(_arr: &mut super::ArrayBase<L, Self>)
     where
         Self: Sized,
         L: super::Layout,
     {
     }
 
-    fn is_unique<L>(arr: &mut super::ArrayBase<L, Self>) -> bool
+    fn is_unique<L>(_arr: &mut super::ArrayBase<L, Self>) -> bool
     where
         Self: Sized,
     {
         true
     }
 
+    /// **Panics** if `offset` is not within the vector, or one past its end.
     fn ref_from_owner_offset(owner: &mut Self::Owned, offset: isize) -> Self::Ref {
-        todo!()
+        owner.storage_at(offset)
     }
 }
 
+/// A backend whose elements may be shared between arrays, and are copied on write.
+///
+/// Cloning an [`ArcArray`](super::ArcArray) only bumps a reference count. The first
+/// mutation through a clone whose elements are shared copies them into an allocation
+/// of its own, leaving the other clones untouched.
 pub struct ArcBackend<T> {
     phantom: PhantomData<T>,
 }
 
-unsafe impl<T: Debug> Backend for ArcBackend<T> {
-    type Ref = NonNull<T>;
+/// Like [`ArcBackend`], but with a reference count that is cheaper to update and cannot
+/// be shared between threads.
+///
+/// An [`RcArray`](super::RcArray) is neither `Send` nor `Sync`.
+pub struct RcBackend<T> {
+    phantom: PhantomData<T>,
+}
+
+macro_rules! impl_shared_backend {
+    ($backend:ident, $owner:ident, $rc:ident) => {
+        /// Mutation needs to copy shared elements, so they must be `Clone`.
+        unsafe impl<T: Clone> Backend for $backend<T> {
+            type Ref = NonNullStorage<T>;
+
+            type Owned = $owner<T>;
 
-    type Owned = ArcOwner<T>;
+            type Elem = T;
+
+            /// If the elements are shared, copy them into a new allocation; see [`unshare`].
+            fn ensure_unique<L>(arr: &mut ArrayBase<L, Self>)
+            where
+                Self: Sized,
+                L: Layout,
+            {
+                if $rc::get_mut(&mut arr.own.0).is_some() {
+                    return;
+                }
+                let (own, storage) = unshare(&arr.own.0, &arr.aref.0.layout, &arr.aref.0.storage);
+                arr.aref.0.storage = storage;
+                arr.own = $owner($rc::new(own));
+            }
+
+            fn is_unique<L>(arr: &mut ArrayBase<L, Self>) -> bool
+            where
+                Self: Sized,
+            {
+                $rc::get_mut(&mut arr.own.0).is_some()
+            }
+
+            /// **Panics** if `offset` is not within the allocation, or one past its end.
+            fn ref_from_owner_offset(owner: &mut Self::Owned, offset: isize) -> Self::Ref {
+                owner.0.storage_at(offset)
+            }
+        }
+    };
+}
+
+impl_shared_backend!(ArcBackend, ArcOwner, Arc);
+impl_shared_backend!(RcBackend, RcOwner, Rc);
+
+/// Copy the elements of an array out of the allocation it shares, returning the copy and
+/// the array's storage within it.
+///
+/// An array that spans at most half of the shared allocation is compacted: only its
+/// own [`memory_extent`](Layout::memory_extent) is copied, so that the new allocation
+/// fits it tightly. Otherwise, the rest of the allocation after the extent is copied
+/// too. Either way, anything before the extent is left behind, so that padding does not
+/// pile up with each copy; the layout is unchanged, so it still describes the copy, and
+/// its origin is aligned as it was; see [`aligned_owner`].
+pub(crate) fn unshare<L: Layout, T: Clone>(
+    owner: &VecOwner<T>,
+    layout: &L,
+    storage: &NonNullStorage<T>,
+) -> (VecOwner<T>, NonNullStorage<T>) {
+    // SAFETY: the origin always lies within the array's own allocation.
+    let origin = unsafe { owner.offset_of(storage.ptr) };
+    let extent = layout
+        .memory_extent()
+        .expect("the extent of an allocated array fits in an isize");
+    let elems = &owner.as_slice()[(origin + extent.start) as usize..];
+    let elems = if layout.size() <= owner.len / 2 {
+        &elems[..extent.len()]
+    } else {
+        elems
+    };
+    let (elems, origin) = (elems.to_vec(), -extent.start);
+    let (own, origin) =
+        aligned_owner(layout, elems, origin).expect("a copy of an aligned array can be aligned");
+    let storage = own.storage_at(origin);
+    (own, storage)
+}
+
+/// Take over `elems`, where the element `origin` places along is to be the origin of
+/// `layout`, moving them if need be so that the origin is aligned as the layout requires.
+///
+/// A layout that needs an aligned origin, as a [`Pitched`](super::Pitched) one does, has
+/// its elements moved after as many copies of the first of them as it takes to align it.
+/// Returns the owner and the origin within it.
+///
+/// Fails if no origin within [`Layout::origin_alignment`] bytes is aligned for `layout`,
+/// as when its elements are of the wrong size.
+pub(crate) fn aligned_owner<L: Layout, T: Clone>(
+    layout: &L,
+    elems: Vec<T>,
+    origin: isize,
+) -> Result<(VecOwner<T>, isize), ShapeError> {
+    let is_aligned = |elems: &Vec<T>, lead: usize| {
+        layout.is_aligned_for(elems.as_ptr().wrapping_offset(origin + lead as isize))
+    };
+    if elems.is_empty() || is_aligned(&elems, 0) {
+        return Ok((VecOwner::from(elems), origin));
+    }
+    let slack = layout
+        .origin_alignment()
+        .div_ceil(mem::size_of::<T>().max(1));
+    // The elements are only pushed once the lead is known, so they never reallocate
+    let mut aligned = Vec::with_capacity(elems.len() + slack);
+    let lead = (0..slack)
+        .find(|&lead| is_aligned(&aligned, lead))
+        .ok_or_else(|| ShapeError::from_kind(ErrorKind::Misaligned))?;
+    aligned.extend(iter::repeat_n(elems[0].clone(), lead));
+    aligned.extend(elems);
+    Ok((VecOwner::from(aligned), origin + lead as isize))
+}
+
+/// A backend whose elements are either borrowed from another array for `'a`, or owned.
+///
+/// Borrowed elements are copied into an allocation of their own on the first mutation,
+/// so a [`CowArray`](super::CowArray) only copies when it has to.
+pub struct CowBackend<'a, T> {
+    phantom: PhantomData<&'a T>,
+}
+
+/// Mutation needs to copy borrowed elements, so they must be `Clone`.
+unsafe impl<'a, T: Clone> Backend for CowBackend<'a, T> {
+    type Ref = NonNullStorage<T>;
+
+    type Owned = CowOwner<'a, T>;
 
     type Elem = T;
 
-    fn ensure_unique<L>(arr: &mut super::ArrayBase<L, Self>)
+    /// If the elements are borrowed, copy them into a new allocation, as `copy_view` describes.
+    fn ensure_unique<L>(arr: &mut ArrayBase<L, Self>)
     where
         Self: Sized,
-        L: super::Layout,
+        L: Layout,
     {
-        if Arc::get_mut(&mut arr.own.0).is_some() {
+        if let CowOwner::Owned(_) = arr.own {
             return;
         }
-        if arr.layout.size() <= arr.own.0.len / 2 {
-            todo!(".to_owned().to_shared()");
+        let RawArrayRefBase {
+            layout, storage, ..
+        } = &arr.aref.0;
+        let (elems, origin, compacted) = copy_view(layout, storage);
+        if let Some(compacted) = compacted {
+            arr.aref.0.layout = compacted;
         }
-        let ptr = arr.as_ptr();
-        let rcvec = &mut arr.own.0;
-        let a_size = mem::size_of::<Self::Elem>() as isize;
-        let our_off = if a_size != 0 {
-            (ptr as isize - Arc::as_ptr(&rcvec) as isize) / a_size
-        } else {
-            0
-        };
-        arr.storage = Self::ref_from_owner_offset(&mut arr.own, our_off);
+        // With no elements, nothing will ever be read
+        let origin = if elems.is_empty() { 0 } else { origin };
+        let (own, origin) = aligned_owner(&arr.aref.0.layout, elems, origin)
+            .expect("a copy of an aligned array can be aligned");
+        arr.aref.0.storage = own.storage_at(origin);
+        arr.own = CowOwner::Owned(own);
+    }
+
+    fn is_unique<L>(arr: &mut ArrayBase<L, Self>) -> bool
+    where
+        Self: Sized,
+    {
+        matches!(arr.own, CowOwner::Owned(_))
     }
 
-    fn is_unique<L>(arr: &mut super::ArrayBase<L, Self>) -> bool
+    /// **Panics** if `offset` is not within the elements, or one past their end.
+    fn ref_from_owner_offset(owner: &mut Self::Owned, offset: isize) -> Self::Ref {
+        owner.storage_at(offset)
+    }
+}
+
+/// Copy the elements of a view into a vector of their own, returning it along with the
+/// origin within it and, if the elements were rearranged, the layout that now describes them.
+///
+/// Only the elements that valid indices map to are read, unless the layout has no
+/// [`compacted`](Layout::compacted) form. If those elements fill the view's
+/// [`memory_extent`](Layout::memory_extent) without gaps, they are copied as they lie, so
+/// the layout is kept. Otherwise, they are copied into the compacted layout, or for layouts
+/// without one, copied along with the gaps between them.
+fn copy_view<L: Layout, T: Clone>(
+    layout: &L,
+    storage: &NonNullStorage<T>,
+) -> (Vec<T>, isize, Option<L>) {
+    let extent = layout
+        .memory_extent()
+        .expect("the extent of an allocated array fits in an isize");
+    // SAFETY: every offset within the extent of a view lies within the elements it
+    // borrows; the caller reads only those `copy_view` allows.
+    let elem = |offset: isize| unsafe { storage.ref_from_offset(offset) }.clone();
+    if layout.is_standard_layout() {
+        return ((0..extent.end).map(elem).collect(), 0, None);
+    }
+    let mut offsets = Vec::new();
+    layout.for_each_offset(|offset| offsets.push(offset));
+    let mut sorted = offsets.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    if sorted.len() == extent.len() {
+        return (sorted.into_iter().map(elem).collect(), -extent.start, None);
+    }
+    let Some(compacted) = layout.compacted() else {
+        return (extent.clone().map(elem).collect(), -extent.start, None);
+    };
+    let compacted_extent = compacted
+        .memory_extent()
+        .expect("a compacted layout is no larger than the original");
+    // Both layouts visit the same indices in the same order, so the offsets pair up
+    let mut targets = Vec::with_capacity(offsets.len());
+    compacted.for_each_offset(|offset| targets.push(offset));
+    let mut pairs: Vec<_> = targets.into_iter().zip(offsets).collect();
+    pairs.sort_unstable_by_key(|&(target, _)| target);
+    pairs.dedup_by_key(|&mut (target, _)| target);
+    assert_eq!(
+        pairs.len(),
+        compacted_extent.len(),
+        "a compacted layout leaves no gaps in its extent"
+    );
+    let elems = pairs.into_iter().map(|(_, offset)| elem(offset)).collect();
+    (elems, -compacted_extent.start, Some(compacted))
+}
+
+/// A backend that keeps up to `CAP` elements inline, so that its arrays never allocate.
+///
+/// A [`StackArray`](super::StackArray) is created, moved, and cloned without touching the
+/// heap. Its elements move along with it, so its storage is pointed back at them before
+/// every access; see [`Backend::refresh`] and [`Backend::refresh_mut`].
+pub struct StackBackend<T, const CAP: usize> {
+    phantom: PhantomData<T>,
+}
+
+unsafe impl<T, const CAP: usize> Backend for StackBackend<T, CAP> {
+    type Ref = InlineStorage<T>;
+
+    type Owned = StackOwner<T, CAP>;
+
+    type Elem = T;
+
+    fn ensure_unique<L>(_arr: &mut ArrayBase<L, Self>)
+    where
+        Self: Sized,
+        L: Layout,
+    {
+    }
+
+    fn is_unique<L>(_arr: &mut ArrayBase<L, Self>) -> bool
     where
         Self: Sized,
     {
-        todo!()
+        true
     }
 
+    /// **Panics** if `offset` is not within the elements, or one past their end.
     fn ref_from_owner_offset(owner: &mut Self::Owned, offset: isize) -> Self::Ref {
-        todo!()
+        owner.storage_at(offset)
+    }
+
+    fn refresh(owner: &Self::Owned, storage: &Self::Ref) {
+        storage.set_base(owner.base());
+    }
+
+    fn refresh_mut(owner: &mut Self::Owned, storage: &mut Self::Ref) {
+        storage.set_base_mut(owner.base_mut());
     }
 }
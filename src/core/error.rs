@@ -0,0 +1,126 @@
+//! Error types
+
+use std::{convert::Infallible, error::Error, fmt};
+
+/// An error related to the shape or layout of an array.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeError {
+    kind: ErrorKind,
+}
+
+/// The kinds of [`ShapeError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A layout has a different number of dimensions than was required.
+    IncompatibleRank { expected: usize, found: usize },
+    /// A layout's memory order cannot be expressed by the requested layout type.
+    IncompatibleLayout,
+    /// An axis has a different length than the layout requires.
+    IncompatibleAxisLength {
+        axis: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A shape cannot be broadcast to the requested shape.
+    IncompatibleBroadcast { from: Vec<usize>, to: Vec<usize> },
+    /// Two shapes cannot be broadcast to a common shape.
+    IncompatibleShapes { lhs: Vec<usize>, rhs: Vec<usize> },
+    /// An axis was selected by a position that the layout does not have.
+    AxisOutOfBounds { axis: usize, ndim: usize },
+    /// An axis was selected by a name that the layout does not have.
+    UnknownAxisName {
+        name: String,
+        names: Vec<Option<String>>,
+    },
+    /// The same name was given to more than one axis.
+    DuplicateAxisName { name: String },
+    /// The size or memory extent of a layout does not fit in an `isize`.
+    Overflow,
+    /// A layout reaches past the end of the allocation it was placed over.
+    OutOfBounds { required: usize, len: usize },
+    /// A layout maps more than one index to the same element, where that is not allowed.
+    AliasedElements,
+    /// The elements a layout was placed over are not aligned as it requires.
+    Misaligned,
+}
+
+impl ShapeError {
+    /// Create a new `ShapeError` of the given kind.
+    pub fn from_kind(kind: ErrorKind) -> Self {
+        ShapeError { kind }
+    }
+
+    /// Return the kind of this error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::IncompatibleRank { expected, found } => write!(
+                f,
+                "incompatible rank: expected {expected} dimensions, found {found}"
+            ),
+            ErrorKind::IncompatibleLayout => write!(f, "incompatible memory layout"),
+            ErrorKind::IncompatibleAxisLength {
+                axis,
+                expected,
+                found,
+            } => write!(
+                f,
+                "incompatible shape: axis {axis} has length {found}, but {expected} is required"
+            ),
+            ErrorKind::IncompatibleBroadcast { from, to } => write!(
+                f,
+                "incompatible shape: {from:?} cannot be broadcast to {to:?}"
+            ),
+            ErrorKind::IncompatibleShapes { lhs, rhs } => write!(
+                f,
+                "incompatible shapes: {lhs:?} and {rhs:?} cannot be broadcast together"
+            ),
+            ErrorKind::AxisOutOfBounds { axis, ndim } => write!(
+                f,
+                "axis {axis} is out of bounds for a layout with {ndim} dimensions"
+            ),
+            ErrorKind::UnknownAxisName { name, names } => {
+                write!(f, "no axis is named {name:?}; the axes are named [")?;
+                for (axis, n) in names.iter().enumerate() {
+                    if axis > 0 {
+                        write!(f, ", ")?;
+                    }
+                    match n {
+                        Some(n) => write!(f, "{n:?}")?,
+                        None => write!(f, "_")?,
+                    }
+                }
+                write!(f, "]")
+            }
+            ErrorKind::DuplicateAxisName { name } => {
+                write!(f, "more than one axis is named {name:?}")
+            }
+            ErrorKind::Overflow => write!(f, "the layout is too large to address"),
+            ErrorKind::OutOfBounds { required, len } => write!(
+                f,
+                "out of bounds: the layout spans {required} elements, but only {len} are available"
+            ),
+            ErrorKind::AliasedElements => {
+                write!(f, "the layout maps more than one index to the same element")
+            }
+            ErrorKind::Misaligned => {
+                write!(f, "the elements are not aligned as the layout requires")
+            }
+        }
+    }
+}
+
+impl Error for ShapeError {}
+
+/// Lets conversions that cannot fail stand in where a fallible one is expected.
+impl From<Infallible> for ShapeError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
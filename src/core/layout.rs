@@ -1,9 +1,42 @@
+mod banded;
+mod broadcast;
 mod changeaxis;
 mod dense;
+mod dynamic;
+mod extents;
+mod index;
+mod indices;
+#[allow(clippy::module_inception)]
 mod layout;
+mod morton;
+mod named;
+mod order;
+mod packed;
 mod patterned;
+mod pitched;
+mod slice;
+mod strided;
+#[cfg(test)]
+mod testing;
+mod tiled;
+mod validate;
 
+pub use banded::*;
+pub use broadcast::*;
 pub use changeaxis::*;
 pub use dense::*;
+pub use dynamic::*;
+pub use extents::*;
+pub use index::*;
+pub use indices::*;
 pub use layout::*;
+pub use morton::*;
+pub use named::*;
+pub use order::*;
+pub use packed::*;
 pub use patterned::*;
+pub use pitched::*;
+pub use slice::*;
+pub use strided::*;
+pub use tiled::*;
+pub use validate::*;
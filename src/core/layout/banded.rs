@@ -0,0 +1,250 @@
+//! Banded matrix layouts, in LAPACK's general band storage format.
+//!
+//! An `m` × `n` matrix with `kl` sub-diagonals and `ku` super-diagonals is stored as
+//! a column-major `(kl + ku + 1)` × `n` array, with each diagonal along a row. With
+//! 0-based indices, element `(i, j)` of the band is at row `ku + i - j` of column `j`:
+//! its offset is `ku + i - j + j * (kl + ku + 1)`.
+//!
+//! The corners of the stored array above the first super-diagonal's start and below the
+//! last sub-diagonal's end belong to no element, just as in LAPACK.
+
+use std::ops::Range;
+
+use super::{contiguous_extent, Layout};
+
+/// A banded matrix layout with `kl` sub-diagonals and `ku` super-diagonals.
+///
+/// Elements outside the band are known to be zero and have no offset:
+/// [`try_offset`](Banded::try_offset) returns `None` for them, while
+/// [`Layout::to_offset`] panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Banded {
+    shape: [usize; 2],
+    kl: usize,
+    ku: usize,
+}
+
+impl Banded {
+    /// Create a layout for a matrix of the given shape, with `kl` sub-diagonals
+    /// and `ku` super-diagonals.
+    pub fn new(shape: [usize; 2], kl: usize, ku: usize) -> Self {
+        Banded { shape, kl, ku }
+    }
+
+    /// The number of rows and columns.
+    pub fn shape(&self) -> &[usize; 2] {
+        &self.shape
+    }
+
+    /// The number of sub-diagonals.
+    pub fn kl(&self) -> usize {
+        self.kl
+    }
+
+    /// The number of super-diagonals.
+    pub fn ku(&self) -> usize {
+        self.ku
+    }
+
+    /// The number of stored rows, LAPACK's `LDAB`: one per diagonal in the band.
+    pub fn ldab(&self) -> usize {
+        self.kl + self.ku + 1
+    }
+
+    /// The number of elements in the band storage, including its unused corners.
+    pub fn band_size(&self) -> usize {
+        self.ldab() * self.shape[1]
+    }
+
+    /// Returns `true` if `(i, j)` is within the band.
+    pub fn contains(&self, [i, j]: [usize; 2]) -> bool {
+        i <= j + self.kl && j <= i + self.ku
+    }
+
+    /// Return the offset of `index`, or `None` if it is outside the band and so is
+    /// known to be zero.
+    ///
+    /// **Panics** if `index` is out of bounds.
+    pub fn try_offset(&self, index: &[usize; 2]) -> Option<usize> {
+        for (axis, (&ix, &len)) in index.iter().zip(&self.shape).enumerate() {
+            assert!(
+                ix < len,
+                "index {ix} is out of bounds for axis {axis} with length {len}"
+            );
+        }
+        let [i, j] = *index;
+        self.contains(*index)
+            .then(|| self.ku + i - j + j * self.ldab())
+    }
+
+    /// Return the elements of diagonal `k`, along with their offsets.
+    ///
+    /// Diagonal `0` is the main diagonal, positive `k` are super-diagonals,
+    /// and negative `k` are sub-diagonals.
+    ///
+    /// **Panics** if `k` is not within the band, i.e. `-kl <= k <= ku`.
+    pub fn diagonal(&self, k: isize) -> Diagonal {
+        assert!(
+            -(self.kl as isize) <= k && k <= self.ku as isize,
+            "diagonal {k} is outside a band with {} sub-diagonals and {} super-diagonals",
+            self.kl,
+            self.ku
+        );
+        let [m, n] = self.shape;
+        let start = if k >= 0 {
+            [0, k as usize]
+        } else {
+            [k.unsigned_abs(), 0]
+        };
+        let len = m.saturating_sub(start[0]).min(n.saturating_sub(start[1]));
+        Diagonal {
+            index: start,
+            offset: (self.ku as isize - k) as usize + start[1] * self.ldab(),
+            stride: self.ldab(),
+            remaining: len,
+        }
+    }
+
+    /// Return every diagonal of the band, from the lowest sub-diagonal to the
+    /// highest super-diagonal.
+    pub fn diagonals(&self) -> impl Iterator<Item = Diagonal> + '_ {
+        (-(self.kl as isize)..=self.ku as isize).map(|k| self.diagonal(k))
+    }
+}
+
+// Only indices in the band are valid, and each has a slot of its own in the band storage.
+unsafe impl Layout for Banded {
+    type Indexer = [usize; 2];
+
+    const NDIM: Option<usize> = Some(2);
+
+    /// **Panics** if `i` is outside the band; see [`try_offset`](Banded::try_offset).
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        match self.try_offset(i) {
+            Some(offset) => offset as isize,
+            None => panic!(
+                "index {i:?} is outside a band with {} sub-diagonals and {} super-diagonals",
+                self.kl, self.ku
+            ),
+        }
+    }
+
+    fn contains_index(&self, i: &Self::Indexer) -> bool {
+        i.iter().zip(&self.shape).all(|(&ix, &len)| ix < len) && self.contains(*i)
+    }
+
+    fn ndim(&self) -> usize {
+        2
+    }
+
+    /// The band storage, including its unused corners; see [`Banded::band_size`].
+    fn memory_extent(&self) -> Option<Range<isize>> {
+        let ldab = self.kl.checked_add(self.ku)?.checked_add(1)?;
+        contiguous_extent(ldab.checked_mul(self.shape[1]))
+    }
+
+    fn slice(&self) -> &[usize] {
+        &self.shape
+    }
+}
+
+/// An iterator over the elements of one diagonal of a [`Banded`] layout,
+/// yielding the index and offset of each.
+///
+/// Created by [`Banded::diagonal`].
+#[derive(Debug, Clone)]
+pub struct Diagonal {
+    index: [usize; 2],
+    offset: usize,
+    /// Stepping down a diagonal moves one column over and stays on the same stored row.
+    stride: usize,
+    remaining: usize,
+}
+
+impl Iterator for Diagonal {
+    type Item = ([usize; 2], usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let item = (self.index, self.offset);
+        self.index = self.index.map(|ix| ix + 1);
+        self.offset += self.stride;
+        self.remaining -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Diagonal {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::layout::testing::sorted_offsets;
+
+    #[test]
+    fn offsets_exist_only_inside_the_band() {
+        let layout = Banded::new([4, 5], 1, 2);
+        assert_eq!(layout.try_offset(&[0, 0]), Some(2));
+        assert_eq!(layout.try_offset(&[1, 0]), Some(3));
+        assert_eq!(layout.try_offset(&[0, 2]), Some(8));
+        assert_eq!(layout.try_offset(&[2, 3]), Some(13));
+        assert_eq!(layout.try_offset(&[0, 3]), None);
+        assert_eq!(layout.try_offset(&[2, 0]), None);
+        assert!(!layout.contains_index(&[0, 3]));
+        assert!(!layout.contains_index(&[4, 4]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn offsets_outside_the_band_panic() {
+        Banded::new([4, 5], 1, 2).to_offset(&[2, 0]);
+    }
+
+    #[test]
+    fn diagonals_step_along_their_stored_row() {
+        let layout = Banded::new([4, 5], 1, 2);
+        let main: Vec<_> = layout.diagonal(0).collect();
+        assert_eq!(main, [([0, 0], 2), ([1, 1], 6), ([2, 2], 10), ([3, 3], 14)]);
+        let upper: Vec<_> = layout.diagonal(2).map(|(_, offset)| offset).collect();
+        assert_eq!(upper, [8, 12, 16]);
+        let lower: Vec<_> = layout.diagonal(-1).map(|(index, _)| index).collect();
+        assert_eq!(lower, [[1, 0], [2, 1], [3, 2]]);
+        assert_eq!(layout.diagonal(1).len(), 4);
+    }
+
+    #[test]
+    fn diagonals_cover_the_band_once() {
+        let layout = Banded::new([4, 5], 1, 2);
+        let mut offsets: Vec<_> = layout
+            .diagonals()
+            .flatten()
+            .map(|(index, offset)| {
+                assert_eq!(layout.try_offset(&index), Some(offset));
+                offset as isize
+            })
+            .collect();
+        offsets.sort_unstable();
+        assert_eq!(offsets, sorted_offsets(&layout));
+    }
+
+    #[test]
+    #[should_panic]
+    fn diagonals_outside_the_band_panic() {
+        Banded::new([4, 5], 1, 2).diagonal(3);
+    }
+
+    #[test]
+    fn extent_covers_the_band_storage() {
+        let layout = Banded::new([4, 5], 1, 2);
+        assert_eq!(layout.memory_extent(), Some(0..20));
+        assert_eq!(layout.band_size(), 20);
+        assert!(sorted_offsets(&layout).iter().all(|&offset| offset < 20));
+        assert_eq!(Banded::new([1, usize::MAX], 1, 1).memory_extent(), None);
+    }
+}
@@ -0,0 +1,239 @@
+//! Broadcasting, following NumPy's rules.
+//!
+//! Shapes are compared from the last axis backwards, and a missing leading axis
+//! counts as an axis of length one. Two axes are compatible if they have the same
+//! length, or if one of them has length one; that axis is then stretched to match
+//! the other by giving it a stride of zero, so no elements are copied.
+
+use crate::core::{ErrorKind, ShapeError};
+
+use super::{AddAxis, Dense, DenseDyn, Layout, Strided};
+
+/// The rank of the shape produced by broadcasting two layouts together.
+///
+/// This is implemented on [`Layout::Indexer`] types, which carry the rank of their layout:
+/// two fixed ranks broadcast to the larger of the two, while a dynamic rank on either
+/// side gives a dynamic result. See [`co_broadcast`].
+pub trait MaxRank<Rhs: ?Sized> {
+    /// The layout of the broadcast shape.
+    type Output: Layout;
+
+    /// Broadcast `lhs` and `rhs` to a common shape.
+    fn co_broadcast_shape(lhs: &[usize], rhs: &[usize]) -> Result<Self::Output, ShapeError>;
+}
+
+/// The layout produced by co-broadcasting `L1` with `L2`.
+pub type CoBroadcast<L1, L2> =
+    <<L1 as Layout>::Indexer as MaxRank<<L2 as Layout>::Indexer>>::Output;
+
+/// Broadcast the shapes of two layouts to a common shape, following NumPy's rules.
+///
+/// The result has the rank of the larger of the two layouts, or is dynamic if either is.
+/// Fails if an axis has a different length in each layout, and neither length is one.
+pub fn co_broadcast<L1, L2>(lhs: &L1, rhs: &L2) -> Result<CoBroadcast<L1, L2>, ShapeError>
+where
+    L1: Layout,
+    L2: Layout,
+    L1::Indexer: MaxRank<L2::Indexer>,
+{
+    <L1::Indexer as MaxRank<L2::Indexer>>::co_broadcast_shape(lhs.slice(), rhs.slice())
+}
+
+/// Layouts that can be broadcast to a shape with `M` axes, without moving any elements.
+///
+/// Axes are matched from the last backwards. Each axis must either have its target
+/// length, or have length one, in which case it is stretched with a stride of zero.
+/// Leading axes that the layout lacks are added with [`AddAxis`], which gives them a
+/// stride of zero as well. A layout cannot be broadcast to fewer axes than it has.
+pub trait Broadcast<const M: usize>: Layout {
+    /// Broadcast the layout to `shape`.
+    ///
+    /// Fails if an axis has neither its length in `shape` nor length one.
+    fn broadcast(&self, shape: [usize; M]) -> Result<Strided<M>, ShapeError>;
+}
+
+impl<const N: usize> Broadcast<N> for Strided<N> {
+    fn broadcast(&self, shape: [usize; N]) -> Result<Strided<N>, ShapeError> {
+        let mut strides = [0; N];
+        for (((&len, &stride), &to), out) in self
+            .shape
+            .iter()
+            .zip(&self.strides)
+            .zip(&shape)
+            .zip(&mut strides)
+        {
+            if len == to {
+                *out = stride;
+            } else if len != 1 {
+                return Err(incompatible_broadcast(&self.shape, &shape));
+            }
+        }
+        Ok(Strided::new(shape, strides))
+    }
+}
+
+macro_rules! impl_broadcast_larger {
+    ($n:literal => $($m:literal),+) => {
+        $(
+            impl Broadcast<$m> for Strided<$n> {
+                fn broadcast(&self, shape: [usize; $m]) -> Result<Strided<$m>, ShapeError> {
+                    // Add the leading axis just before the existing ones, at its full length
+                    self.add_axis(0, shape[$m - $n - 1])
+                        .broadcast(shape)
+                        .map_err(|_| incompatible_broadcast(&self.shape, &shape))
+                }
+            }
+        )+
+    };
+}
+
+impl_broadcast_larger!(0 => 1, 2, 3, 4, 5, 6);
+impl_broadcast_larger!(1 => 2, 3, 4, 5, 6);
+impl_broadcast_larger!(2 => 3, 4, 5, 6);
+impl_broadcast_larger!(3 => 4, 5, 6);
+impl_broadcast_larger!(4 => 5, 6);
+impl_broadcast_larger!(5 => 6);
+
+fn incompatible_broadcast(from: &[usize], to: &[usize]) -> ShapeError {
+    ShapeError::from_kind(ErrorKind::IncompatibleBroadcast {
+        from: from.to_vec(),
+        to: to.to_vec(),
+    })
+}
+
+/// Write the broadcast of `lhs` and `rhs` into `out`, which must be as long as the longer of the two.
+fn co_broadcast_into(lhs: &[usize], rhs: &[usize], out: &mut [usize]) -> Result<(), ShapeError> {
+    // The length of the `i`th axis from the end, where missing axes have length one
+    let len_at = |shape: &[usize], i: usize| shape.len().checked_sub(i + 1).map_or(1, |j| shape[j]);
+    for (i, len) in out.iter_mut().rev().enumerate() {
+        *len = match (len_at(lhs, i), len_at(rhs, i)) {
+            (l, r) if l == r || r == 1 => l,
+            (1, r) => r,
+            _ => {
+                return Err(ShapeError::from_kind(ErrorKind::IncompatibleShapes {
+                    lhs: lhs.to_vec(),
+                    rhs: rhs.to_vec(),
+                }))
+            }
+        };
+    }
+    Ok(())
+}
+
+impl<const N: usize> MaxRank<[usize; N]> for [usize; N] {
+    type Output = Dense<N>;
+
+    fn co_broadcast_shape(lhs: &[usize], rhs: &[usize]) -> Result<Self::Output, ShapeError> {
+        let mut shape = [0; N];
+        co_broadcast_into(lhs, rhs, &mut shape).map(|()| Dense(shape))
+    }
+}
+
+impl<const N: usize> MaxRank<[usize]> for [usize; N] {
+    type Output = DenseDyn;
+
+    fn co_broadcast_shape(lhs: &[usize], rhs: &[usize]) -> Result<Self::Output, ShapeError> {
+        co_broadcast_dyn(lhs, rhs)
+    }
+}
+
+impl<const N: usize> MaxRank<[usize; N]> for [usize] {
+    type Output = DenseDyn;
+
+    fn co_broadcast_shape(lhs: &[usize], rhs: &[usize]) -> Result<Self::Output, ShapeError> {
+        co_broadcast_dyn(lhs, rhs)
+    }
+}
+
+impl MaxRank<[usize]> for [usize] {
+    type Output = DenseDyn;
+
+    fn co_broadcast_shape(lhs: &[usize], rhs: &[usize]) -> Result<Self::Output, ShapeError> {
+        co_broadcast_dyn(lhs, rhs)
+    }
+}
+
+fn co_broadcast_dyn(lhs: &[usize], rhs: &[usize]) -> Result<DenseDyn, ShapeError> {
+    let mut shape = vec![0; lhs.len().max(rhs.len())];
+    co_broadcast_into(lhs, rhs, &mut shape).map(|()| DenseDyn::new(&shape))
+}
+
+macro_rules! impl_max_rank {
+    ($smaller:literal => $($larger:literal),+) => {
+        $(
+            impl MaxRank<[usize; $larger]> for [usize; $smaller] {
+                type Output = Dense<$larger>;
+
+                fn co_broadcast_shape(lhs: &[usize], rhs: &[usize]) -> Result<Self::Output, ShapeError> {
+                    let mut shape = [0; $larger];
+                    co_broadcast_into(lhs, rhs, &mut shape).map(|()| Dense(shape))
+                }
+            }
+
+            impl MaxRank<[usize; $smaller]> for [usize; $larger] {
+                type Output = Dense<$larger>;
+
+                fn co_broadcast_shape(lhs: &[usize], rhs: &[usize]) -> Result<Self::Output, ShapeError> {
+                    let mut shape = [0; $larger];
+                    co_broadcast_into(lhs, rhs, &mut shape).map(|()| Dense(shape))
+                }
+            }
+        )+
+    };
+}
+
+impl_max_rank!(0 => 1, 2, 3, 4, 5, 6);
+impl_max_rank!(1 => 2, 3, 4, 5, 6);
+impl_max_rank!(2 => 3, 4, 5, 6);
+impl_max_rank!(3 => 4, 5, 6);
+impl_max_rank!(4 => 5, 6);
+impl_max_rank!(5 => 6);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broadcast_stretches_axes_of_length_one() {
+        let layout = Strided::new([3, 1], [1, 7]).broadcast([3, 4]).unwrap();
+        assert_eq!(layout.shape(), &[3, 4]);
+        assert_eq!(layout.strides(), &[1, 0]);
+        assert!(!layout.has_unique_offsets());
+    }
+
+    #[test]
+    fn broadcast_adds_leading_axes() {
+        let layout = Strided::new([3], [2]).broadcast([2, 4, 3]).unwrap();
+        assert_eq!(layout.shape(), &[2, 4, 3]);
+        assert_eq!(layout.strides(), &[0, 0, 2]);
+        assert_eq!(layout.to_offset(&[1, 3, 2]), 4);
+    }
+
+    #[test]
+    fn broadcast_reports_the_original_shape() {
+        let err = Strided::new([3], [1]).broadcast([2, 4]).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::IncompatibleBroadcast {
+                from: vec![3],
+                to: vec![2, 4],
+            }
+        );
+    }
+
+    #[test]
+    fn co_broadcast_follows_numpy() {
+        let shape = co_broadcast(&Dense::new([4, 1, 3]), &Dense::new([5, 1])).unwrap();
+        assert_eq!(shape, Dense::new([4, 5, 3]));
+        let shape = co_broadcast(&Dense::new([2]), &DenseDyn::new(&[3, 1])).unwrap();
+        assert_eq!(shape, DenseDyn::new(&[3, 2]));
+        let err = co_broadcast(&Dense::new([2, 3]), &Dense::new([4])).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::IncompatibleShapes {
+                lhs: vec![2, 3],
+                rhs: vec![4],
+            }
+        );
+    }
+}
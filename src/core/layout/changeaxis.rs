@@ -1,4 +1,6 @@
-use super::Layout;
+use crate::core::ShapeError;
+
+use super::{AxisSelector, Layout};
 
 /// Array shape with a next smaller dimension.
 ///
@@ -8,7 +10,21 @@ pub trait RemoveAxis: Layout {
     /// Next smaller dimension (if applicable)
     type Smaller: Layout + AddAxis;
 
+    /// Get the smaller dimension corresponding to removing the dimension at `axis`.
+    ///
+    /// If `axis` is not less than the number of existing dimensions, removes the last one.
     fn remove_axis(&self, axis: usize) -> Self::Smaller;
+
+    /// Remove the axis picked out by `axis`, which may be a position or, for [`Named`](super::Named)
+    /// layouts, a name.
+    ///
+    /// Unlike [`RemoveAxis::remove_axis`], fails if there is no such axis.
+    fn remove_axis_by<A: AxisSelector<Self>>(&self, axis: A) -> Result<Self::Smaller, ShapeError>
+    where
+        Self: Sized,
+    {
+        axis.select(self).map(|axis| self.remove_axis(axis))
+    }
 }
 
 pub trait AddAxis: Layout {
@@ -21,3 +37,311 @@ pub trait AddAxis: Layout {
     /// after the last existing dimension.
     fn add_axis(&self, axis: usize, length: usize) -> Self::Larger;
 }
+
+/// Layouts that can keep only the elements at one index along an axis, without moving
+/// any of them.
+///
+/// Layouts keep their own type where they can. Picking out an inner index of a
+/// contiguous layout leaves gaps, so those give a strided layout instead.
+pub trait IndexAxis: Layout {
+    /// The layout left once the axis is removed.
+    type Smaller: Layout;
+
+    /// Keep only the elements at `index` along `axis`, removing that axis.
+    ///
+    /// Returns the smaller layout, and the offset of its origin from the old one.
+    ///
+    /// **Panics** if `axis` or `index` is out of bounds.
+    fn index_axis(&self, axis: usize, index: usize) -> (Self::Smaller, isize);
+}
+
+/// Layouts that can gain an axis of length one without moving any elements.
+pub trait InsertAxis: Layout {
+    /// The layout with the extra axis.
+    type Larger: Layout;
+
+    /// Add an axis of length one before `axis`, or after the last axis if `axis` is the
+    /// number of axes.
+    ///
+    /// The origin does not move.
+    ///
+    /// **Panics** if `axis` is greater than the number of axes.
+    fn insert_axis(&self, axis: usize) -> Self::Larger;
+}
+
+/// Layouts whose axes can be reordered without moving any elements.
+///
+/// Layouts keep their own type where they can. Reordering the axes of a contiguous
+/// layout takes its elements out of order, so those give a strided layout instead.
+pub trait PermuteAxes: Layout + Sized {
+    /// The layout with its axes reordered.
+    type Permuted: Layout;
+
+    /// Reorder the axes, so that axis `i` of the result is axis `axes[i]` of `self`.
+    ///
+    /// **Panics** if `axes` does not contain each axis exactly once.
+    fn permuted_axes(&self, axes: &[usize]) -> Self::Permuted;
+
+    /// Reorder the axes, picking each out by position or, for [`Named`](super::Named)
+    /// layouts, by name.
+    ///
+    /// Fails if any of `axes` does not exist; see [`PermuteAxes::permuted_axes`].
+    fn permuted_axes_by<A: AxisSelector<Self>>(
+        &self,
+        axes: &[A],
+    ) -> Result<Self::Permuted, ShapeError> {
+        let axes = axes
+            .iter()
+            .map(|axis| axis.select(self))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(self.permuted_axes(&axes))
+    }
+}
+
+/// **Panics** if `axes` does not contain each of `0..ndim` exactly once.
+pub(super) fn assert_permutation(axes: &[usize], ndim: usize) {
+    assert_eq!(
+        axes.len(),
+        ndim,
+        "permutation has {} axes, but the layout has {ndim} dimensions",
+        axes.len()
+    );
+    let mut seen = vec![false; ndim];
+    for &axis in axes {
+        assert!(
+            axis < ndim && !seen[axis],
+            "{axes:?} is not a permutation of the axes"
+        );
+        seen[axis] = true;
+    }
+}
+
+/// **Panics** if `axis` is out of bounds for `shape`, or `index` is out of bounds for `axis`.
+pub(super) fn assert_axis_index(shape: &[usize], axis: usize, index: usize) {
+    let ndim = shape.len();
+    assert!(
+        axis < ndim,
+        "axis {axis} is out of bounds for a layout with {ndim} dimensions"
+    );
+    let len = shape[axis];
+    assert!(
+        index < len,
+        "index {index} is out of bounds for axis {axis} with length {len}"
+    );
+}
+
+/// **Panics** if a new axis cannot be inserted before `axis` in a layout with `ndim` axes.
+pub(super) fn assert_insert_axis(axis: usize, ndim: usize) {
+    assert!(
+        axis <= ndim,
+        "cannot insert axis {axis} into a layout with {ndim} dimensions"
+    );
+}
+
+/// Copy `shape` into `smaller`, leaving out `axis` (or the last axis, if `axis` is out of bounds).
+///
+/// `smaller` must be exactly one element shorter than `shape`.
+pub(super) fn remove_axis_into<T: Copy>(shape: &[T], axis: usize, smaller: &mut [T]) {
+    let axis = axis.min(smaller.len());
+    smaller[..axis].copy_from_slice(&shape[..axis]);
+    smaller[axis..].copy_from_slice(&shape[axis + 1..]);
+}
+
+/// Copy `shape` into `larger`, inserting `value` at `axis` (or at the end, if `axis` is out of bounds).
+///
+/// `larger` must be exactly one element longer than `shape`.
+pub(super) fn add_axis_into<T: Copy>(shape: &[T], axis: usize, value: T, larger: &mut [T]) {
+    let axis = axis.min(shape.len());
+    larger[..axis].copy_from_slice(&shape[..axis]);
+    larger[axis] = value;
+    larger[axis + 1..].copy_from_slice(&shape[axis..]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        ColMajor, Dense, DenseDyn, Extents2, IndexFromSlice, Named, Pitched, Static2, Strided,
+        StridedDyn, DYN,
+    };
+
+    fn offset_of<L: Layout>(layout: &L, index: &[usize]) -> isize {
+        L::Indexer::with_slice(index, |index| layout.to_offset(index))
+    }
+
+    /// Every index into `shape`, in row-major order.
+    fn all_indices(shape: &[usize]) -> Vec<Vec<usize>> {
+        let mut indices = vec![vec![]];
+        for &len in shape {
+            indices = indices
+                .into_iter()
+                .flat_map(|index| {
+                    (0..len).map(move |ix| {
+                        let mut index = index.clone();
+                        index.push(ix);
+                        index
+                    })
+                })
+                .collect();
+        }
+        indices
+    }
+
+    /// Checks that every element of the smaller layout is where `layout` put it.
+    fn check_index_axis<L: IndexAxis>(layout: &L, axis: usize, index: usize) -> L::Smaller {
+        let (smaller, origin) = layout.index_axis(axis, index);
+        let mut shape = layout.slice().to_vec();
+        shape.remove(axis);
+        assert_eq!(smaller.slice(), &shape[..]);
+        for ix in all_indices(&shape) {
+            let mut full = ix.clone();
+            full.insert(axis, index);
+            assert_eq!(
+                origin + offset_of(&smaller, &ix),
+                offset_of(layout, &full),
+                "{ix:?}"
+            );
+        }
+        smaller
+    }
+
+    /// Checks that every element of the larger layout is where `layout` put it.
+    fn check_insert_axis<L: InsertAxis>(layout: &L, axis: usize) -> L::Larger {
+        let larger = layout.insert_axis(axis);
+        let mut shape = layout.slice().to_vec();
+        shape.insert(axis, 1);
+        assert_eq!(larger.slice(), &shape[..]);
+        for ix in all_indices(layout.slice()) {
+            let mut full = ix.clone();
+            full.insert(axis, 0);
+            assert_eq!(offset_of(&larger, &full), offset_of(layout, &ix), "{ix:?}");
+        }
+        larger
+    }
+
+    /// Checks that every element of the permuted layout is where `layout` put it.
+    fn check_permuted_axes<L: PermuteAxes>(layout: &L, axes: &[usize]) -> L::Permuted {
+        let permuted = layout.permuted_axes(axes);
+        let shape: Vec<_> = axes.iter().map(|&axis| layout.slice()[axis]).collect();
+        assert_eq!(permuted.slice(), &shape[..]);
+        for ix in all_indices(&shape) {
+            let mut full = vec![0; ix.len()];
+            for (&axis, &i) in axes.iter().zip(&ix) {
+                full[axis] = i;
+            }
+            assert_eq!(
+                offset_of(&permuted, &ix),
+                offset_of(layout, &full),
+                "{ix:?}"
+            );
+        }
+        permuted
+    }
+
+    #[test]
+    fn permuting_contiguous_layouts_makes_them_strided() {
+        for axes in [[0, 1, 2], [2, 0, 1], [1, 2, 0], [2, 1, 0]] {
+            let _: Strided<3> = check_permuted_axes(&Dense::new([2, 3, 4]), &axes);
+            let _: Strided<3> = check_permuted_axes(&ColMajor::new([2, 3, 4]), &axes);
+            let _: StridedDyn = check_permuted_axes(&DenseDyn::new(&[2, 3, 4]), &axes);
+            let _: Strided<3> = check_permuted_axes(&Strided::new([2, 3, 4], [-12, 4, 1]), &axes);
+            let _: StridedDyn =
+                check_permuted_axes(&StridedDyn::new(&[2, 3, 4], [1, -2, 6].as_ref()), &axes);
+        }
+    }
+
+    #[test]
+    fn permuting_by_name_moves_the_names() {
+        let layout = Named::new(Dense::new([2, 3, 4]), ["a", "b", "c"]).unwrap();
+        let permuted = layout.permuted_axes_by(&["c", "a", "b"]).unwrap();
+        assert_eq!(permuted, check_permuted_axes(&layout, &[2, 0, 1]));
+        assert_eq!(
+            permuted.names().collect::<Vec<_>>(),
+            [Some("c"), Some("a"), Some("b")]
+        );
+        assert!(layout.permuted_axes_by(&["c", "a", "z"]).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a permutation")]
+    fn permuted_axes_checks_for_repeats() {
+        Dense::new([2, 3]).permuted_axes(&[1, 1]);
+    }
+
+    #[test]
+    fn strided_layouts_stay_strided() {
+        let layout = Strided::new([2, 3, 4], [-12, 4, 1]);
+        for axis in 0..3 {
+            let _: Strided<2> = check_index_axis(&layout, axis, 1);
+            let _: Strided<4> = check_insert_axis(&layout, axis);
+        }
+        let _: Strided<4> = check_insert_axis(&layout, 3);
+
+        let layout = StridedDyn::new(&[2, 3, 4], &[1, -2, 6]);
+        for axis in 0..3 {
+            let _: StridedDyn = check_index_axis(&layout, axis, 1);
+            let _: StridedDyn = check_insert_axis(&layout, axis);
+        }
+    }
+
+    #[test]
+    fn contiguous_layouts_stay_contiguous_when_inserting() {
+        let dense = Dense::new([2, 3, 4]);
+        let col = ColMajor::new([2, 3, 4]);
+        let dynamic = DenseDyn::new(&[2, 3, 4]);
+        for axis in 0..=3 {
+            let _: Dense<4> = check_insert_axis(&dense, axis);
+            let _: ColMajor<4> = check_insert_axis(&col, axis);
+            let _: DenseDyn = check_insert_axis(&dynamic, axis);
+        }
+        for axis in 0..3 {
+            let _: Strided<2> = check_index_axis(&dense, axis, 1);
+            let _: Strided<2> = check_index_axis(&col, axis, 1);
+            let _: StridedDyn = check_index_axis(&dynamic, axis, 1);
+        }
+    }
+
+    #[test]
+    fn extents_lose_their_static_lengths() {
+        let layout = Static2::<3, 4>::new();
+        let _: Strided<1> = check_index_axis(&layout, 1, 2);
+        let _: Dense<3> = check_insert_axis(&layout, 1);
+        let layout = Extents2::<3, DYN>::new([3, 5]).unwrap();
+        let _: Strided<1> = check_index_axis(&layout, 0, 2);
+        let _: Dense<3> = check_insert_axis(&layout, 2);
+    }
+
+    #[test]
+    fn pitched_rows_keep_their_padding() {
+        let layout = Pitched::<f32, 3>::new([2, 3, 3], 16);
+        for axis in 0..3 {
+            let _: Strided<2> = check_index_axis(&layout, axis, 1);
+        }
+        let larger: Strided<4> = check_insert_axis(&layout, 3);
+        assert_eq!(larger.strides(), &[12, 4, 1, 0]);
+    }
+
+    #[test]
+    fn names_follow_their_axes() {
+        let layout = Named::new(Dense::new([2, 3, 4]), ["a", "b", "c"]).unwrap();
+        let smaller = check_index_axis(&layout, 1, 2);
+        assert_eq!(smaller.names().collect::<Vec<_>>(), [Some("a"), Some("c")]);
+        let larger = check_insert_axis(&layout, 1);
+        assert_eq!(
+            larger.names().collect::<Vec<_>>(),
+            [Some("a"), None, Some("b"), Some("c")]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 is out of bounds for axis 1")]
+    fn index_axis_checks_the_index() {
+        Dense::new([2, 3]).index_axis(1, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot insert axis 3")]
+    fn insert_axis_checks_the_axis() {
+        Dense::new([2, 3]).insert_axis(3);
+    }
+}
@@ -1,20 +1,88 @@
-use super::{AddAxis, Layout, Patterned, RemoveAxis};
-
+use super::{
+    add_axis_into, assert_insert_axis, contiguous_offset, contiguous_strides, remove_axis_into,
+    AddAxis, ColMajor, IndexAxis, InsertAxis, Layout, Order, Patterned, PermuteAxes, RemoveAxis,
+    Strided,
+};
+
+/// A contiguous, row-major layout with a fixed number of dimensions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Dense<const N: usize>(pub(super) [usize; N]);
 
-impl<const N: usize> Layout for Dense<N> {
+impl<const N: usize> Dense<N> {
+    /// Create a row-major layout with the given shape.
+    pub fn new(shape: [usize; N]) -> Self {
+        Dense(shape)
+    }
+
+    /// Return the strides implied by the shape.
+    pub fn strides(&self) -> [isize; N] {
+        let mut strides = [0; N];
+        contiguous_strides(&self.0, Order::RowMajor, &mut strides);
+        strides
+    }
+
+    /// Reverse the order of the axes, giving a column-major layout over the same data.
+    pub fn reversed_axes(mut self) -> ColMajor<N> {
+        self.0.reverse();
+        ColMajor(self.0)
+    }
+}
+
+// In-bounds indices map one-to-one onto `0..size`, in row-major order.
+unsafe impl<const N: usize> Layout for Dense<N> {
+    type Indexer = [usize; N];
+
     const NDIM: Option<usize> = Some(N);
 
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        contiguous_offset(&self.0, i, Order::RowMajor) as isize
+    }
+
     fn ndim(&self) -> usize {
         N
     }
 
+    fn is_standard_layout(&self) -> bool {
+        true
+    }
+
     fn slice(&self) -> &[usize] {
         &self.0
     }
+}
+
+impl<const N: usize> IndexAxis for Dense<N>
+where
+    Strided<N>: IndexAxis,
+{
+    type Smaller = <Strided<N> as IndexAxis>::Smaller;
+
+    /// The remaining elements are only contiguous when indexing the first axis, so the
+    /// result is always strided.
+    fn index_axis(&self, axis: usize, index: usize) -> (Self::Smaller, isize) {
+        Strided::from(*self).index_axis(axis, index)
+    }
+}
+
+impl<const N: usize> PermuteAxes for Dense<N> {
+    type Permuted = Strided<N>;
+
+    /// The elements are only in row-major order when no axis moves, so the result is
+    /// always strided.
+    fn permuted_axes(&self, axes: &[usize]) -> Self::Permuted {
+        Strided::from(*self).permuted_axes(axes)
+    }
+}
+
+impl<const N: usize> InsertAxis for Dense<N>
+where
+    Dense<N>: AddAxis,
+{
+    type Larger = <Self as AddAxis>::Larger;
 
-    fn slice_mut(&mut self) -> &mut [usize] {
-        &mut self.0
+    fn insert_axis(&self, axis: usize) -> Self::Larger {
+        assert_insert_axis(axis, N);
+        self.add_axis(axis, 1)
     }
 }
 
@@ -30,67 +98,102 @@ macro_rules! impl_patterned {
 
         impl From<$pattern> for $name {
             fn from(value: $pattern) -> Self {
-                Dense { 0: value.into() }
+                Dense(value.into())
             }
         }
     };
 }
 
-impl Patterned for Dense<0> {
-    type Pattern = ();
+macro_rules! impl_remove_axis {
+    ($n:literal, $smaller:literal) => {
+        impl RemoveAxis for Dense<$n> {
+            type Smaller = Dense<$smaller>;
 
-    fn as_pattern(&self) -> Self::Pattern {
-        ()
-    }
+            fn remove_axis(&self, axis: usize) -> Self::Smaller {
+                let mut smaller = [0; $smaller];
+                remove_axis_into(&self.0, axis, &mut smaller);
+                Dense(smaller)
+            }
+        }
+    };
 }
 
-impl From<()> for Dense<0> {
-    fn from(_value: ()) -> Self {
-        Dense { 0: [] }
-    }
+macro_rules! impl_add_axis {
+    ($n:literal, $larger:literal) => {
+        impl AddAxis for Dense<$n> {
+            type Larger = Dense<$larger>;
+
+            fn add_axis(&self, axis: usize, length: usize) -> Self::Larger {
+                let mut larger = [0; $larger];
+                add_axis_into(&self.0, axis, length, &mut larger);
+                Dense(larger)
+            }
+        }
+    };
 }
 
-impl RemoveAxis for Dense<1> {
-    type Smaller = Dense<0>;
+impl Patterned for Dense<0> {
+    type Pattern = ();
 
-    fn remove_axis(&self, _axis: usize) -> Self::Smaller {
-        Dense { 0: [] }
-    }
+    fn as_pattern(&self) -> Self::Pattern {}
 }
 
-impl RemoveAxis for Dense<2> {
-    type Smaller = Dense<1>;
-
-    fn remove_axis(&self, axis: usize) -> Self::Smaller {
-        match axis {
-            0 => Dense { 0: [self.0[1]] },
-            1.. => Dense { 0: [self.0[0]] },
-        }
+impl From<()> for Dense<0> {
+    fn from(_value: ()) -> Self {
+        Dense([])
     }
 }
 
 impl_patterned!(Dense<1>, (usize,));
 impl_patterned!(Dense<2>, (usize, usize));
+impl_patterned!(Dense<3>, (usize, usize, usize));
+impl_patterned!(Dense<4>, (usize, usize, usize, usize));
+impl_patterned!(Dense<5>, (usize, usize, usize, usize, usize));
+impl_patterned!(Dense<6>, (usize, usize, usize, usize, usize, usize));
+
+impl_remove_axis!(1, 0);
+impl_remove_axis!(2, 1);
+impl_remove_axis!(3, 2);
+impl_remove_axis!(4, 3);
+impl_remove_axis!(5, 4);
+impl_remove_axis!(6, 5);
+
+impl_add_axis!(0, 1);
+impl_add_axis!(1, 2);
+impl_add_axis!(2, 3);
+impl_add_axis!(3, 4);
+impl_add_axis!(4, 5);
+impl_add_axis!(5, 6);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removing_an_axis_at_the_largest_rank() {
+        let layout = Dense::new([2, 3, 4, 5, 6, 7]);
+        assert_eq!(layout.remove_axis(0), Dense::new([3, 4, 5, 6, 7]));
+        assert_eq!(layout.remove_axis(3), Dense::new([2, 3, 4, 6, 7]));
+        assert_eq!(layout.remove_axis(5), Dense::new([2, 3, 4, 5, 6]));
+        assert_eq!(Dense::new([4]).remove_axis(0), Dense::new([]));
+    }
 
-impl AddAxis for Dense<0> {
-    type Larger = Dense<1>;
-
-    fn add_axis(&self, _axis: usize, length: usize) -> Self::Larger {
-        Dense { 0: [length] }
+    #[test]
+    fn adding_an_axis_at_the_smallest_rank() {
+        assert_eq!(Dense::new([]).add_axis(0, 4), Dense::new([4]));
+        assert_eq!(Dense::new([]).insert_axis(0), Dense::new([1]));
+        let layout = Dense::new([2, 3, 4, 5, 6]);
+        assert_eq!(layout.add_axis(5, 7), Dense::new([2, 3, 4, 5, 6, 7]));
+        assert_eq!(layout.add_axis(0, 7), Dense::new([7, 2, 3, 4, 5, 6]));
     }
-}
 
-impl AddAxis for Dense<1> {
-    type Larger = Dense<2>;
-
-    fn add_axis(&self, axis: usize, length: usize) -> Self::Larger {
-        match axis {
-            0 => Dense {
-                0: [length, self.0[0]],
-            },
-            1.. => Dense {
-                0: [self.0[0], length],
-            },
-        }
+    #[test]
+    fn patterns_round_trip_at_every_rank() {
+        let layout = Dense::new([2, 3, 4, 5, 6, 7]);
+        assert_eq!(layout.as_pattern(), (2, 3, 4, 5, 6, 7));
+        assert_eq!(Dense::from(layout.as_pattern()), layout);
+        assert_eq!(Dense::from((5,)).as_pattern(), (5,));
+        let () = Dense::new([]).as_pattern();
+        assert_eq!(Dense::from(()), Dense::new([]));
     }
 }
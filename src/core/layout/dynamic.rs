@@ -0,0 +1,437 @@
+use std::{fmt, ops::Range};
+
+use crate::core::{ErrorKind, ShapeError};
+
+use super::{
+    add_axis_into, assert_axis_index, assert_insert_axis, assert_permutation, contiguous_offset,
+    contiguous_strides, is_layout_in_order, remove_axis_into, strided_extent, strided_offset,
+    strided_unique_offsets, AddAxis, Dense, IndexAxis, InsertAxis, Layout, Order, Patterned,
+    PermuteAxes, RemoveAxis, Strided,
+};
+
+/// The number of axes a [`DenseDyn`] stores inline before spilling onto the heap.
+const INLINE_CAP: usize = 4;
+
+/// Axis lengths, kept inline when there are few enough of them.
+#[derive(Clone, PartialEq, Eq)]
+enum DynRepr {
+    Inline(u32, [usize; INLINE_CAP]),
+    Alloc(Box<[usize]>),
+}
+
+impl DynRepr {
+    fn from_slice(xs: &[usize]) -> Self {
+        if xs.len() <= INLINE_CAP {
+            let mut arr = [0; INLINE_CAP];
+            arr[..xs.len()].copy_from_slice(xs);
+            DynRepr::Inline(xs.len() as u32, arr)
+        } else {
+            DynRepr::Alloc(xs.into())
+        }
+    }
+
+    fn as_slice(&self) -> &[usize] {
+        match self {
+            DynRepr::Inline(len, arr) => &arr[..*len as usize],
+            DynRepr::Alloc(xs) => xs,
+        }
+    }
+}
+
+/// A dense layout whose number of dimensions is only known at runtime.
+///
+/// Shapes with up to four axes are stored inline; larger shapes spill onto the heap.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DenseDyn(DynRepr);
+
+impl DenseDyn {
+    /// Create a dynamic-rank layout with the given shape.
+    pub fn new(shape: &[usize]) -> Self {
+        DenseDyn(DynRepr::from_slice(shape))
+    }
+}
+
+impl fmt::Debug for DenseDyn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DenseDyn").field(&self.slice()).finish()
+    }
+}
+
+// Like `Dense`, in-bounds indices map one-to-one onto `0..size`, in row-major order.
+unsafe impl Layout for DenseDyn {
+    type Indexer = [usize];
+
+    const NDIM: Option<usize> = None;
+
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        contiguous_offset(self.slice(), i, Order::RowMajor) as isize
+    }
+
+    fn ndim(&self) -> usize {
+        self.slice().len()
+    }
+
+    fn is_standard_layout(&self) -> bool {
+        true
+    }
+
+    fn slice(&self) -> &[usize] {
+        self.0.as_slice()
+    }
+}
+
+impl Patterned for DenseDyn {
+    /// Dynamic-rank layouts have no tuple form, so their pattern is a vector of lengths.
+    type Pattern = Vec<usize>;
+
+    fn as_pattern(&self) -> Self::Pattern {
+        self.slice().to_vec()
+    }
+}
+
+impl RemoveAxis for DenseDyn {
+    type Smaller = DenseDyn;
+
+    /// **Panics** if the layout has no dimensions.
+    fn remove_axis(&self, axis: usize) -> Self::Smaller {
+        let shape = self.slice();
+        assert!(
+            !shape.is_empty(),
+            "cannot remove an axis from a zero-dimensional layout"
+        );
+        let mut smaller = vec![0; shape.len() - 1];
+        remove_axis_into(shape, axis, &mut smaller);
+        DenseDyn::new(&smaller)
+    }
+}
+
+impl AddAxis for DenseDyn {
+    type Larger = DenseDyn;
+
+    fn add_axis(&self, axis: usize, length: usize) -> Self::Larger {
+        let shape = self.slice();
+        let mut larger = vec![0; shape.len() + 1];
+        add_axis_into(shape, axis, length, &mut larger);
+        DenseDyn::new(&larger)
+    }
+}
+
+impl IndexAxis for DenseDyn {
+    type Smaller = StridedDyn;
+
+    /// See [`Dense::index_axis`].
+    fn index_axis(&self, axis: usize, index: usize) -> (Self::Smaller, isize) {
+        StridedDyn::from(self.clone()).index_axis(axis, index)
+    }
+}
+
+impl PermuteAxes for DenseDyn {
+    type Permuted = StridedDyn;
+
+    /// See [`Dense::permuted_axes`].
+    fn permuted_axes(&self, axes: &[usize]) -> Self::Permuted {
+        StridedDyn::from(self.clone()).permuted_axes(axes)
+    }
+}
+
+impl InsertAxis for DenseDyn {
+    type Larger = DenseDyn;
+
+    fn insert_axis(&self, axis: usize) -> Self::Larger {
+        assert_insert_axis(axis, self.ndim());
+        self.add_axis(axis, 1)
+    }
+}
+
+impl From<&[usize]> for DenseDyn {
+    fn from(value: &[usize]) -> Self {
+        DenseDyn::new(value)
+    }
+}
+
+impl From<Vec<usize>> for DenseDyn {
+    fn from(value: Vec<usize>) -> Self {
+        DenseDyn::new(&value)
+    }
+}
+
+impl<const N: usize> From<Dense<N>> for DenseDyn {
+    fn from(value: Dense<N>) -> Self {
+        DenseDyn::new(&value.0)
+    }
+}
+
+impl<const N: usize> TryFrom<DenseDyn> for Dense<N> {
+    type Error = ShapeError;
+
+    /// Fails if `value` does not have exactly `N` dimensions.
+    fn try_from(value: DenseDyn) -> Result<Self, Self::Error> {
+        let shape = value.slice();
+        <[usize; N]>::try_from(shape).map(Dense).map_err(|_| {
+            ShapeError::from_kind(ErrorKind::IncompatibleRank {
+                expected: N,
+                found: shape.len(),
+            })
+        })
+    }
+}
+
+/// A strided layout whose number of dimensions is only known at runtime.
+///
+/// This is the dynamic-rank counterpart of [`Strided`], and what a [`DenseDyn`] becomes
+/// once its elements are no longer contiguous.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StridedDyn {
+    shape: Box<[usize]>,
+    strides: Box<[isize]>,
+}
+
+impl StridedDyn {
+    /// Create a layout from a shape and the stride of each of its axes.
+    ///
+    /// **Panics** if `shape` and `strides` have different lengths.
+    pub fn new(shape: &[usize], strides: &[isize]) -> Self {
+        assert_eq!(
+            shape.len(),
+            strides.len(),
+            "a layout needs one stride per axis"
+        );
+        StridedDyn {
+            shape: shape.into(),
+            strides: strides.into(),
+        }
+    }
+
+    /// The length of each axis.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// The distance, in elements, between consecutive indices along each axis.
+    pub fn strides(&self) -> &[isize] {
+        &self.strides
+    }
+}
+
+// Like `Strided`, the extent and uniqueness are worked out from the strides.
+unsafe impl Layout for StridedDyn {
+    type Indexer = [usize];
+
+    const NDIM: Option<usize> = None;
+
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        strided_offset(&self.shape, &self.strides, i)
+    }
+
+    fn ndim(&self) -> usize {
+        self.shape.len()
+    }
+
+    /// See [`Strided::memory_extent`].
+    fn memory_extent(&self) -> Option<Range<isize>> {
+        strided_extent(&self.shape, &self.strides)
+    }
+
+    /// See [`Strided::has_unique_offsets`].
+    fn has_unique_offsets(&self) -> bool {
+        let mut axes: Vec<usize> = (0..self.ndim()).collect();
+        axes.sort_by_key(|&i| self.strides[i].unsigned_abs());
+        strided_unique_offsets(&self.shape, &self.strides, axes)
+    }
+
+    fn is_standard_layout(&self) -> bool {
+        is_layout_in_order(&self.shape, &self.strides, Order::RowMajor)
+    }
+
+    /// See [`Strided::compacted`].
+    fn compacted(&self) -> Option<Self> {
+        let mut strides = vec![0; self.ndim()];
+        contiguous_strides(&self.shape, Order::RowMajor, &mut strides);
+        Some(StridedDyn::new(&self.shape, &strides))
+    }
+
+    fn slice(&self) -> &[usize] {
+        &self.shape
+    }
+}
+
+impl RemoveAxis for StridedDyn {
+    type Smaller = StridedDyn;
+
+    /// **Panics** if the layout has no dimensions.
+    fn remove_axis(&self, axis: usize) -> Self::Smaller {
+        let ndim = self.ndim();
+        assert!(
+            ndim > 0,
+            "cannot remove an axis from a zero-dimensional layout"
+        );
+        let mut shape = vec![0; ndim - 1];
+        let mut strides = vec![0; ndim - 1];
+        remove_axis_into(&self.shape, axis, &mut shape);
+        remove_axis_into(&self.strides, axis, &mut strides);
+        StridedDyn::new(&shape, &strides)
+    }
+}
+
+impl AddAxis for StridedDyn {
+    type Larger = StridedDyn;
+
+    /// The new axis has a stride of zero; see [`Strided::add_axis`].
+    fn add_axis(&self, axis: usize, length: usize) -> Self::Larger {
+        let ndim = self.ndim();
+        let mut shape = vec![0; ndim + 1];
+        let mut strides = vec![0; ndim + 1];
+        add_axis_into(&self.shape, axis, length, &mut shape);
+        add_axis_into(&self.strides, axis, 0, &mut strides);
+        StridedDyn::new(&shape, &strides)
+    }
+}
+
+impl IndexAxis for StridedDyn {
+    type Smaller = StridedDyn;
+
+    fn index_axis(&self, axis: usize, index: usize) -> (Self::Smaller, isize) {
+        assert_axis_index(&self.shape, axis, index);
+        (self.remove_axis(axis), index as isize * self.strides[axis])
+    }
+}
+
+impl PermuteAxes for StridedDyn {
+    type Permuted = Self;
+
+    fn permuted_axes(&self, axes: &[usize]) -> Self {
+        assert_permutation(axes, self.ndim());
+        StridedDyn {
+            shape: axes.iter().map(|&axis| self.shape[axis]).collect(),
+            strides: axes.iter().map(|&axis| self.strides[axis]).collect(),
+        }
+    }
+}
+
+impl InsertAxis for StridedDyn {
+    type Larger = StridedDyn;
+
+    fn insert_axis(&self, axis: usize) -> Self::Larger {
+        assert_insert_axis(axis, self.ndim());
+        self.add_axis(axis, 1)
+    }
+}
+
+impl From<DenseDyn> for StridedDyn {
+    fn from(value: DenseDyn) -> Self {
+        let shape = value.slice();
+        let mut strides = vec![0; shape.len()];
+        contiguous_strides(shape, Order::RowMajor, &mut strides);
+        StridedDyn::new(shape, &strides)
+    }
+}
+
+impl<const N: usize> From<Strided<N>> for StridedDyn {
+    fn from(value: Strided<N>) -> Self {
+        StridedDyn::new(&value.shape, &value.strides)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_spill_onto_the_heap_past_four_axes() {
+        let small = DenseDyn::new(&[2, 3, 4, 5]);
+        assert!(matches!(small.0, DynRepr::Inline(4, _)));
+        assert_eq!(small.slice(), &[2, 3, 4, 5]);
+        let large = DenseDyn::new(&[2, 3, 4, 5, 6]);
+        assert!(matches!(large.0, DynRepr::Alloc(_)));
+        assert_eq!(large.slice(), &[2, 3, 4, 5, 6]);
+        assert!(matches!(DenseDyn::new(&[]).0, DynRepr::Inline(0, _)));
+    }
+
+    #[test]
+    fn removing_and_adding_axes_cross_the_inline_limit() {
+        let large = DenseDyn::new(&[2, 3, 4, 5, 6]);
+        let smaller = large.remove_axis(2);
+        assert_eq!(smaller, DenseDyn::new(&[2, 3, 5, 6]));
+        assert!(matches!(smaller.0, DynRepr::Inline(4, _)));
+        let larger = smaller.add_axis(2, 4);
+        assert_eq!(larger, large);
+        assert!(matches!(larger.0, DynRepr::Alloc(_)));
+        assert_eq!(smaller.insert_axis(4).slice(), &[2, 3, 5, 6, 1]);
+    }
+
+    #[test]
+    fn large_shapes_are_row_major() {
+        let layout = DenseDyn::new(&[2, 3, 4, 5, 6]);
+        assert_eq!(layout.size(), 720);
+        assert_eq!(layout.memory_extent(), Some(0..720));
+        assert_eq!(layout.to_offset(&[1, 2, 3, 4, 5]), 719);
+        assert_eq!(layout.to_offset(&[1, 0, 0, 0, 0]), 360);
+        assert_eq!(layout.to_offset(&[0, 0, 1, 0, 1]), 31);
+        let layout = DenseDyn::new(&[1, 2, 1, 2, 1, 2]);
+        assert_eq!(layout.to_offset(&[0, 1, 0, 1, 0, 1]), 7);
+        assert_eq!(layout.memory_extent(), Some(0..8));
+    }
+
+    #[test]
+    fn dense_dyn_converts_to_the_matching_fixed_rank() {
+        assert_eq!(
+            Dense::try_from(DenseDyn::new(&[2, 3])),
+            Ok(Dense::new([2, 3]))
+        );
+        let large = Dense::<5>::try_from(DenseDyn::new(&[2, 3, 4, 5, 6]));
+        assert_eq!(large, Ok(Dense::new([2, 3, 4, 5, 6])));
+        let err = Dense::<3>::try_from(DenseDyn::new(&[2, 3])).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::IncompatibleRank {
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            DenseDyn::from(Dense::new([2, 3, 4, 5, 6])),
+            DenseDyn::new(&[2, 3, 4, 5, 6])
+        );
+    }
+
+    #[test]
+    fn strided_dyn_agrees_with_strided() {
+        let fixed = Strided::new([3, 4], [-4, 1]);
+        let dynamic = StridedDyn::from(fixed);
+        assert_eq!(dynamic.memory_extent(), fixed.memory_extent());
+        assert_eq!(dynamic.has_unique_offsets(), fixed.has_unique_offsets());
+        for i in 0..3 {
+            for j in 0..4 {
+                assert_eq!(dynamic.to_offset(&[i, j]), fixed.to_offset(&[i, j]));
+            }
+        }
+    }
+
+    #[test]
+    fn dense_dyn_becomes_strided_dyn() {
+        let layout = StridedDyn::from(DenseDyn::new(&[2, 3, 4]));
+        assert_eq!(layout.strides(), &[12, 4, 1]);
+        assert!(layout.is_standard_layout());
+        let smaller = layout.remove_axis(1);
+        assert_eq!(smaller.shape(), &[2, 4]);
+        assert_eq!(smaller.strides(), &[12, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index has 1 entries, but the layout has 2 dimensions")]
+    fn strided_dyn_offsets_need_every_axis() {
+        StridedDyn::new(&[2, 3], &[3, 1]).to_offset(&[1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index has 3 entries, but the layout has 2 dimensions")]
+    fn strided_dyn_offsets_need_no_more_than_every_axis() {
+        StridedDyn::new(&[2, 3], &[3, 1]).to_offset(&[1, 2, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "one stride per axis")]
+    fn strided_dyn_needs_a_stride_per_axis() {
+        StridedDyn::new(&[2, 3], &[1]);
+    }
+}
@@ -0,0 +1,359 @@
+//! Layouts with axis lengths fixed at compile time, like the static extents of C++23 `mdspan`.
+//!
+//! `StaticN` layouts know every axis length at compile time and are zero-sized.
+//! `ExtentsN` layouts mix static and dynamic axes: an axis whose length is [`DYN`]
+//! is only known at runtime, while every other axis is fixed to its const parameter.
+
+use crate::core::{ErrorKind, ShapeError};
+
+use super::{contiguous_offset, Dense, IndexAxis, InsertAxis, Layout, Order, Patterned, Strided};
+
+/// Marks an axis of an `ExtentsN` layout whose length is only known at runtime.
+pub const DYN: usize = usize::MAX;
+
+/// Check `shape` against the `extents`, where each static extent fixes the length of its axis.
+fn check_extents(extents: &[usize], shape: &[usize]) -> Result<(), ShapeError> {
+    for (axis, (&expected, &found)) in extents.iter().zip(shape).enumerate() {
+        if expected != DYN && expected != found {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleAxisLength {
+                axis,
+                expected,
+                found,
+            }));
+        }
+    }
+    Ok(())
+}
+
+macro_rules! impl_extents {
+    ($static:ident, $extents:ident, $n:literal, [$($c:ident),+], $pattern:ty) => {
+        /// A contiguous, row-major layout whose axis lengths are all known at compile time.
+        ///
+        /// This type is zero-sized; none of the extents may be [`DYN`].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct $static<$(const $c: usize),+>(());
+
+        impl<$(const $c: usize),+> $static<$($c),+> {
+            const SHAPE: [usize; $n] = [$($c),+];
+
+            const CHECK: () = {
+                let mut axis = 0;
+                while axis < $n {
+                    assert!(Self::SHAPE[axis] != DYN, "static layouts cannot have dynamic axes");
+                    axis += 1;
+                }
+            };
+
+            /// Create the layout.
+            pub fn new() -> Self {
+                let () = Self::CHECK;
+                $static(())
+            }
+        }
+
+        impl<$(const $c: usize),+> Default for $static<$($c),+> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        // In-bounds indices map one-to-one onto `0..size`, in row-major order.
+        unsafe impl<$(const $c: usize),+> Layout for $static<$($c),+> {
+            type Indexer = [usize; $n];
+
+            const NDIM: Option<usize> = Some($n);
+
+            fn to_offset(&self, i: &Self::Indexer) -> isize {
+                contiguous_offset(&Self::SHAPE, i, Order::RowMajor) as isize
+            }
+
+            fn ndim(&self) -> usize {
+                $n
+            }
+
+            fn size(&self) -> usize {
+                1 $(* $c)+
+            }
+
+            fn is_standard_layout(&self) -> bool {
+                true
+            }
+
+            fn slice(&self) -> &[usize] {
+                &Self::SHAPE
+            }
+        }
+
+        impl<$(const $c: usize),+> Patterned for $static<$($c),+> {
+            type Pattern = $pattern;
+
+            fn as_pattern(&self) -> Self::Pattern {
+                Self::SHAPE.into()
+            }
+        }
+
+        impl<$(const $c: usize),+> From<$pattern> for $static<$($c),+> {
+            /// **Panics** if `value` does not match the static extents.
+            fn from(value: $pattern) -> Self {
+                let shape: [usize; $n] = value.into();
+                match check_extents(&Self::SHAPE, &shape) {
+                    Ok(()) => Self::new(),
+                    Err(e) => panic!("{e}"),
+                }
+            }
+        }
+
+        impl<$(const $c: usize),+> IndexAxis for $static<$($c),+> {
+            type Smaller = <Strided<$n> as IndexAxis>::Smaller;
+
+            /// See [`Dense::index_axis`].
+            fn index_axis(&self, axis: usize, index: usize) -> (Self::Smaller, isize) {
+                Strided::from(Dense::from(*self)).index_axis(axis, index)
+            }
+        }
+
+        impl<$(const $c: usize),+> From<$static<$($c),+>> for Dense<$n> {
+            fn from(_value: $static<$($c),+>) -> Self {
+                Dense($static::<$($c),+>::SHAPE)
+            }
+        }
+
+        impl<$(const $c: usize),+> TryFrom<Dense<$n>> for $static<$($c),+> {
+            type Error = ShapeError;
+
+            fn try_from(value: Dense<$n>) -> Result<Self, Self::Error> {
+                check_extents(&Self::SHAPE, &value.0).map(|()| Self::new())
+            }
+        }
+
+        /// A contiguous, row-major layout that mixes static and dynamic axes.
+        ///
+        /// Each axis is either fixed to its const parameter, or is [`DYN`] and takes its
+        /// length at runtime. Offsets are computed from the static lengths wherever they
+        /// are known.
+        ///
+        /// Unlike `mdspan`, the whole shape is stored rather than only the dynamic
+        /// lengths. An array sized by the number of `DYN` parameters would need
+        /// `generic_const_exprs`, which is not stable, and [`Layout::slice`] must lend
+        /// out every length as one slice in any case.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct $extents<$(const $c: usize),+>([usize; $n]);
+
+        impl<$(const $c: usize),+> $extents<$($c),+> {
+            const EXTENTS: [usize; $n] = [$($c),+];
+
+            /// Create a layout with the given shape.
+            ///
+            /// Fails if `shape` disagrees with any of the static extents.
+            pub fn new(shape: [usize; $n]) -> Result<Self, ShapeError> {
+                check_extents(&Self::EXTENTS, &shape).map(|()| $extents(shape))
+            }
+
+            /// Return the shape, taking static lengths from the type.
+            fn lengths(&self) -> [usize; $n] {
+                let mut shape = Self::EXTENTS;
+                for (len, &dynamic) in shape.iter_mut().zip(&self.0) {
+                    if *len == DYN {
+                        *len = dynamic;
+                    }
+                }
+                shape
+            }
+        }
+
+        // In-bounds indices map one-to-one onto `0..size`, in row-major order.
+        unsafe impl<$(const $c: usize),+> Layout for $extents<$($c),+> {
+            type Indexer = [usize; $n];
+
+            const NDIM: Option<usize> = Some($n);
+
+            fn to_offset(&self, i: &Self::Indexer) -> isize {
+                contiguous_offset(&self.lengths(), i, Order::RowMajor) as isize
+            }
+
+            fn ndim(&self) -> usize {
+                $n
+            }
+
+            fn is_standard_layout(&self) -> bool {
+                true
+            }
+
+            fn slice(&self) -> &[usize] {
+                &self.0
+            }
+        }
+
+        impl<$(const $c: usize),+> Patterned for $extents<$($c),+> {
+            type Pattern = $pattern;
+
+            fn as_pattern(&self) -> Self::Pattern {
+                self.0.into()
+            }
+        }
+
+        impl<$(const $c: usize),+> From<$pattern> for $extents<$($c),+> {
+            /// **Panics** if `value` does not match the static extents.
+            fn from(value: $pattern) -> Self {
+                match Self::new(value.into()) {
+                    Ok(extents) => extents,
+                    Err(e) => panic!("{e}"),
+                }
+            }
+        }
+
+        impl<$(const $c: usize),+> IndexAxis for $extents<$($c),+> {
+            type Smaller = <Strided<$n> as IndexAxis>::Smaller;
+
+            /// See [`Dense::index_axis`].
+            fn index_axis(&self, axis: usize, index: usize) -> (Self::Smaller, isize) {
+                Strided::from(Dense::from(*self)).index_axis(axis, index)
+            }
+        }
+
+        impl<$(const $c: usize),+> From<$extents<$($c),+>> for Dense<$n> {
+            fn from(value: $extents<$($c),+>) -> Self {
+                Dense(value.0)
+            }
+        }
+
+        impl<$(const $c: usize),+> TryFrom<Dense<$n>> for $extents<$($c),+> {
+            type Error = ShapeError;
+
+            fn try_from(value: Dense<$n>) -> Result<Self, Self::Error> {
+                Self::new(value.0)
+            }
+        }
+
+        impl<$(const $c: usize),+> From<$static<$($c),+>> for $extents<$($c),+> {
+            fn from(_value: $static<$($c),+>) -> Self {
+                $extents($static::<$($c),+>::SHAPE)
+            }
+        }
+    };
+}
+
+// Kept apart from `impl_extents!`, as six axes is as large as layouts go.
+macro_rules! impl_insert_axis {
+    ($($layout:ident, $n:literal, [$($c:ident),+]);+ $(;)?) => {
+        $(
+            impl<$(const $c: usize),+> InsertAxis for $layout<$($c),+> {
+                type Larger = <Dense<$n> as InsertAxis>::Larger;
+
+                /// The new axis may fall between any two static axes, so the result
+                /// keeps its lengths at runtime.
+                fn insert_axis(&self, axis: usize) -> Self::Larger {
+                    Dense::from(*self).insert_axis(axis)
+                }
+            }
+        )+
+    };
+}
+
+impl_extents!(Static1, Extents1, 1, [A], (usize,));
+impl_extents!(Static2, Extents2, 2, [A, B], (usize, usize));
+impl_extents!(Static3, Extents3, 3, [A, B, C], (usize, usize, usize));
+impl_extents!(
+    Static4,
+    Extents4,
+    4,
+    [A, B, C, D],
+    (usize, usize, usize, usize)
+);
+impl_extents!(
+    Static5,
+    Extents5,
+    5,
+    [A, B, C, D, E],
+    (usize, usize, usize, usize, usize)
+);
+impl_extents!(
+    Static6,
+    Extents6,
+    6,
+    [A, B, C, D, E, F],
+    (usize, usize, usize, usize, usize, usize)
+);
+
+impl_insert_axis!(
+    Static1, 1, [A];
+    Extents1, 1, [A];
+    Static2, 2, [A, B];
+    Extents2, 2, [A, B];
+    Static3, 3, [A, B, C];
+    Extents3, 3, [A, B, C];
+    Static4, 4, [A, B, C, D];
+    Extents4, 4, [A, B, C, D];
+    Static5, 5, [A, B, C, D, E];
+    Extents5, 5, [A, B, C, D, E];
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::layout::testing::sorted_offsets;
+
+    #[test]
+    fn static_layouts_are_zero_sized_and_row_major() {
+        let layout = Static2::<2, 3>::new();
+        assert_eq!(std::mem::size_of_val(&layout), 0);
+        assert_eq!(layout.slice(), &[2, 3]);
+        assert_eq!(layout.size(), 6);
+        assert_eq!(layout.to_offset(&[1, 2]), 5);
+        assert_eq!(sorted_offsets(&layout), (0..6).collect::<Vec<_>>());
+        assert_eq!(Dense::from(layout), Dense::new([2, 3]));
+    }
+
+    #[test]
+    fn static_layouts_only_match_their_own_shape() {
+        assert_eq!(
+            Static2::<2, 3>::try_from(Dense::new([2, 3])),
+            Ok(Static2::new())
+        );
+        let err = Static2::<2, 3>::try_from(Dense::new([2, 4])).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::IncompatibleAxisLength {
+                axis: 1,
+                expected: 3,
+                found: 4
+            }
+        );
+    }
+
+    #[test]
+    fn dynamic_axes_take_their_length_at_runtime() {
+        let layout = Extents2::<DYN, 3>::new([5, 3]).unwrap();
+        assert_eq!(layout.slice(), &[5, 3]);
+        assert_eq!(layout.to_offset(&[4, 2]), 14);
+        assert_eq!(sorted_offsets(&layout), (0..15).collect::<Vec<_>>());
+        assert_eq!(
+            Extents2::<2, DYN>::new([2, 7]).unwrap().to_offset(&[1, 0]),
+            7
+        );
+        assert_eq!(
+            Extents2::from(Static2::<4, 1>::new()),
+            Extents2::<4, 1>::new([4, 1]).unwrap()
+        );
+    }
+
+    #[test]
+    fn static_axes_of_mixed_layouts_are_checked() {
+        let err = Extents3::<DYN, 3, DYN>::new([1, 2, 3]).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::IncompatibleAxisLength {
+                axis: 1,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert!(Extents2::<DYN, 3>::try_from(Dense::new([9, 3])).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "axis 0")]
+    fn conversion_from_a_mismatched_pattern_panics() {
+        let _ = Extents2::<2, DYN>::from((3, 3));
+    }
+}
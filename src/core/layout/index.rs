@@ -0,0 +1,178 @@
+//! Types that can be used to index into an array.
+
+use super::Layout;
+
+/// A [`Layout::Indexer`] that can be borrowed from a slice with one entry per axis, so
+/// that code generic over layouts can build indices of its own.
+pub trait IndexFromSlice {
+    /// Call `f` with `index` as this type.
+    ///
+    /// **Panics** if `index` does not have one entry per axis of this type.
+    fn with_slice<R>(index: &[usize], f: impl FnOnce(&Self) -> R) -> R;
+}
+
+impl<const N: usize> IndexFromSlice for [usize; N] {
+    fn with_slice<R>(index: &[usize], f: impl FnOnce(&Self) -> R) -> R {
+        let index: [usize; N] = index
+            .try_into()
+            .unwrap_or_else(|_| panic!("index has {} axes, but {N} are needed", index.len()));
+        f(&index)
+    }
+}
+
+impl IndexFromSlice for [usize] {
+    fn with_slice<R>(index: &[usize], f: impl FnOnce(&Self) -> R) -> R {
+        f(index)
+    }
+}
+
+/// A type that can index into arrays with layout `L`.
+///
+/// Fixed-rank layouts, whose [`Layout::Indexer`] is `[usize; N]`, accept that array or
+/// the matching tuple, as given by [`Patterned`](super::Patterned); one-dimensional
+/// layouts also accept a bare `usize`. Dynamic-rank layouts accept a `&[usize]` or a
+/// `Vec<usize>`, as their [`indices`](Layout::indices) yield.
+pub trait NdIndex<L: Layout> {
+    /// Return the offset of the element at this index, or `None` if it is not a valid
+    /// index into `layout`.
+    fn index_checked(&self, layout: &L) -> Option<isize>;
+
+    /// Return the offset of the element at this index, without checking it beyond
+    /// what [`Layout::to_offset`] itself checks.
+    fn index_unchecked(&self, layout: &L) -> isize;
+}
+
+impl<L, const N: usize> NdIndex<L> for [usize; N]
+where
+    L: Layout<Indexer = [usize; N]>,
+{
+    fn index_checked(&self, layout: &L) -> Option<isize> {
+        layout.contains_index(self).then(|| layout.to_offset(self))
+    }
+
+    fn index_unchecked(&self, layout: &L) -> isize {
+        layout.to_offset(self)
+    }
+}
+
+impl<L> NdIndex<L> for &[usize]
+where
+    L: Layout<Indexer = [usize]>,
+{
+    fn index_checked(&self, layout: &L) -> Option<isize> {
+        layout.contains_index(self).then(|| layout.to_offset(self))
+    }
+
+    fn index_unchecked(&self, layout: &L) -> isize {
+        layout.to_offset(self)
+    }
+}
+
+impl<L> NdIndex<L> for Vec<usize>
+where
+    L: Layout<Indexer = [usize]>,
+{
+    fn index_checked(&self, layout: &L) -> Option<isize> {
+        self.as_slice().index_checked(layout)
+    }
+
+    fn index_unchecked(&self, layout: &L) -> isize {
+        self.as_slice().index_unchecked(layout)
+    }
+}
+
+impl<L> NdIndex<L> for usize
+where
+    L: Layout<Indexer = [usize; 1]>,
+{
+    fn index_checked(&self, layout: &L) -> Option<isize> {
+        [*self].index_checked(layout)
+    }
+
+    fn index_unchecked(&self, layout: &L) -> isize {
+        [*self].index_unchecked(layout)
+    }
+}
+
+macro_rules! impl_tuple_index {
+    ($n:literal, $pattern:ty) => {
+        impl<L> NdIndex<L> for $pattern
+        where
+            L: Layout<Indexer = [usize; $n]>,
+        {
+            fn index_checked(&self, layout: &L) -> Option<isize> {
+                <[usize; $n]>::from(*self).index_checked(layout)
+            }
+
+            fn index_unchecked(&self, layout: &L) -> isize {
+                <[usize; $n]>::from(*self).index_unchecked(layout)
+            }
+        }
+    };
+}
+
+impl<L> NdIndex<L> for ()
+where
+    L: Layout<Indexer = [usize; 0]>,
+{
+    fn index_checked(&self, layout: &L) -> Option<isize> {
+        [].index_checked(layout)
+    }
+
+    fn index_unchecked(&self, layout: &L) -> isize {
+        [].index_unchecked(layout)
+    }
+}
+
+impl_tuple_index!(1, (usize,));
+impl_tuple_index!(2, (usize, usize));
+impl_tuple_index!(3, (usize, usize, usize));
+impl_tuple_index!(4, (usize, usize, usize, usize));
+impl_tuple_index!(5, (usize, usize, usize, usize, usize));
+impl_tuple_index!(6, (usize, usize, usize, usize, usize, usize));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Dense, DenseDyn, Strided};
+
+    #[test]
+    fn fixed_rank_layouts_take_arrays_and_tuples() {
+        let layout = Dense::new([2, 3]);
+        assert_eq!([1, 2].index_checked(&layout), Some(5));
+        assert_eq!((1, 2).index_checked(&layout), Some(5));
+        assert_eq!((2, 0).index_checked(&layout), None);
+        assert_eq!((0, 3).index_checked(&layout), None);
+        assert_eq!((1, 0).index_unchecked(&layout), 3);
+
+        let strided = Strided::new([3, 2, 2], [-4, 2, 1]);
+        assert_eq!((2, 1, 0).index_checked(&strided), Some(-6));
+        assert_eq!((2, 1, 2).index_checked(&strided), None);
+    }
+
+    #[test]
+    fn small_ranks_take_bare_indices() {
+        let layout = Dense::new([4]);
+        assert_eq!(3.index_checked(&layout), Some(3));
+        assert_eq!(4.index_checked(&layout), None);
+        assert_eq!((3,).index_checked(&layout), Some(3));
+        assert_eq!(().index_checked(&Dense::new([])), Some(0));
+    }
+
+    #[test]
+    fn dynamic_rank_layouts_take_slices_and_vectors() {
+        let layout = DenseDyn::new(&[2, 3, 4]);
+        assert_eq!([1, 2, 3].as_ref().index_checked(&layout), Some(23));
+        assert_eq!(vec![1, 0, 0].index_checked(&layout), Some(12));
+        assert_eq!(vec![2, 0, 0].index_checked(&layout), None);
+        // A slice with the wrong number of axes is not an index at all
+        assert_eq!([1, 2].as_ref().index_checked(&layout), None);
+        assert_eq!(vec![0, 0, 0, 0].index_checked(&layout), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "index has 2 axes, but 3 are needed")]
+    fn fixed_rank_indexers_need_one_entry_per_axis() {
+        <[usize; 3]>::with_slice(&[0, 1], |_| ());
+    }
+}
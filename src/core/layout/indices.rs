@@ -0,0 +1,296 @@
+//! Iterating over every index of a layout.
+
+use std::{fmt::Debug, iter::FusedIterator};
+
+use super::{Dense, DenseDyn, Order, Patterned};
+
+/// An owned, growable form of a [`Layout::Indexer`](super::Layout::Indexer), used to step
+/// through indices and to turn them into [`Patterned`] values.
+pub trait IndexBuf {
+    /// A mutable index with one entry per axis.
+    type Buf: AsRef<[usize]> + AsMut<[usize]> + Clone + Debug;
+
+    /// The pattern-matching friendly form of an index, as given by [`Patterned`].
+    type Pattern;
+
+    /// Create an index of all zeros with `ndim` axes.
+    fn zeros(ndim: usize) -> Self::Buf;
+
+    /// Convert an index into its pattern.
+    fn to_pattern(index: &Self::Buf) -> Self::Pattern;
+}
+
+impl<const N: usize> IndexBuf for [usize; N]
+where
+    Dense<N>: Patterned,
+{
+    type Buf = [usize; N];
+    type Pattern = <Dense<N> as Patterned>::Pattern;
+
+    fn zeros(_ndim: usize) -> Self::Buf {
+        [0; N]
+    }
+
+    fn to_pattern(index: &Self::Buf) -> Self::Pattern {
+        Dense(*index).as_pattern()
+    }
+}
+
+impl IndexBuf for [usize] {
+    type Buf = Vec<usize>;
+    type Pattern = <DenseDyn as Patterned>::Pattern;
+
+    fn zeros(ndim: usize) -> Self::Buf {
+        vec![0; ndim]
+    }
+
+    fn to_pattern(index: &Self::Buf) -> Self::Pattern {
+        index.clone()
+    }
+}
+
+/// An iterator over every index of a layout, in row-major ("C") or column-major ("F") order.
+///
+/// Created by [`Layout::indices`](super::Layout::indices) and
+/// [`Layout::indices_in`](super::Layout::indices_in).
+#[derive(Debug)]
+pub struct Indices<I: IndexBuf + ?Sized> {
+    shape: I::Buf,
+    order: Order,
+    /// The next index from the front, at linear position `front`.
+    front_index: I::Buf,
+    /// The next index from the back, at linear position `back - 1`.
+    back_index: I::Buf,
+    front: usize,
+    back: usize,
+}
+
+impl<I: IndexBuf + ?Sized> Indices<I> {
+    pub(super) fn new(shape: &[usize], order: Order) -> Self {
+        let mut buf = I::zeros(shape.len());
+        buf.as_mut().copy_from_slice(shape);
+        let len = shape.iter().product();
+        let mut indices = Indices {
+            front_index: I::zeros(shape.len()),
+            back_index: I::zeros(shape.len()),
+            shape: buf,
+            order,
+            front: 0,
+            back: len,
+        };
+        if len > 0 {
+            indices.back_index = indices.index_at(len - 1);
+        }
+        indices
+    }
+
+    /// Compute the index at linear position `pos`, without stepping through the ones before it.
+    fn index_at(&self, mut pos: usize) -> I::Buf {
+        let mut index = I::zeros(self.shape.as_ref().len());
+        let mut place = |(ix, &len): (&mut usize, &usize)| {
+            *ix = pos % len;
+            pos /= len;
+        };
+        let axes = index.as_mut().iter_mut().zip(self.shape.as_ref());
+        match self.order {
+            Order::RowMajor => axes.rev().for_each(&mut place),
+            Order::ColumnMajor => axes.for_each(&mut place),
+        }
+        index
+    }
+
+    /// Step `index` forwards to the next index in `order`.
+    ///
+    /// The last index wraps around to the first.
+    fn step_forward(index: &mut [usize], shape: &[usize], order: Order) {
+        let mut carry = |(ix, &len): (&mut usize, &usize)| {
+            *ix += 1;
+            if *ix == len {
+                *ix = 0;
+                true
+            } else {
+                false
+            }
+        };
+        let mut axes = index.iter_mut().zip(shape);
+        match order {
+            Order::RowMajor => axes.rev().all(&mut carry),
+            Order::ColumnMajor => axes.all(&mut carry),
+        };
+    }
+
+    /// Step `index` backwards to the previous index in `order`.
+    ///
+    /// The first index wraps around to the last.
+    fn step_backward(index: &mut [usize], shape: &[usize], order: Order) {
+        let mut borrow = |(ix, &len): (&mut usize, &usize)| {
+            if *ix == 0 {
+                *ix = len - 1;
+                true
+            } else {
+                *ix -= 1;
+                false
+            }
+        };
+        let mut axes = index.iter_mut().zip(shape);
+        match order {
+            Order::RowMajor => axes.rev().all(&mut borrow),
+            Order::ColumnMajor => axes.all(&mut borrow),
+        };
+    }
+}
+
+impl<I: IndexBuf + ?Sized> Clone for Indices<I> {
+    fn clone(&self) -> Self {
+        Indices {
+            shape: self.shape.clone(),
+            order: self.order,
+            front_index: self.front_index.clone(),
+            back_index: self.back_index.clone(),
+            front: self.front,
+            back: self.back,
+        }
+    }
+}
+
+impl<I: IndexBuf + ?Sized> Iterator for Indices<I> {
+    type Item = I::Pattern;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        let item = I::to_pattern(&self.front_index);
+        self.front += 1;
+        Self::step_forward(self.front_index.as_mut(), self.shape.as_ref(), self.order);
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.back - self.front {
+            self.front = self.back;
+            return None;
+        }
+        self.front += n;
+        self.front_index = self.index_at(self.front);
+        self.next()
+    }
+}
+
+impl<I: IndexBuf + ?Sized> DoubleEndedIterator for Indices<I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        let item = I::to_pattern(&self.back_index);
+        self.back -= 1;
+        Self::step_backward(self.back_index.as_mut(), self.shape.as_ref(), self.order);
+        Some(item)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.back - self.front {
+            self.back = self.front;
+            return None;
+        }
+        self.back -= n;
+        self.back_index = self.index_at(self.back - 1);
+        self.next_back()
+    }
+}
+
+impl<I: IndexBuf + ?Sized> ExactSizeIterator for Indices<I> {}
+
+impl<I: IndexBuf + ?Sized> FusedIterator for Indices<I> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Layout;
+
+    const SHAPE: [usize; 3] = [2, 3, 4];
+
+    /// Every index of `SHAPE` in `order`, from plain nested loops.
+    fn nested_loops(order: Order) -> Vec<Vec<usize>> {
+        let mut indices = Vec::new();
+        for a in 0..SHAPE[0] {
+            for b in 0..SHAPE[1] {
+                for c in 0..SHAPE[2] {
+                    indices.push(vec![a, b, c]);
+                }
+            }
+        }
+        if order == Order::ColumnMajor {
+            indices.sort_by_key(|index| index.iter().rev().cloned().collect::<Vec<_>>());
+        }
+        indices
+    }
+
+    fn indices(order: Order) -> Indices<[usize]> {
+        DenseDyn::new(&SHAPE).indices_in(order)
+    }
+
+    #[test]
+    fn forwards_and_backwards_match_nested_loops() {
+        for order in [Order::RowMajor, Order::ColumnMajor] {
+            let expected = nested_loops(order);
+            assert_eq!(indices(order).collect::<Vec<_>>(), expected);
+            let mut reversed = expected.clone();
+            reversed.reverse();
+            assert_eq!(indices(order).rev().collect::<Vec<_>>(), reversed);
+        }
+    }
+
+    #[test]
+    fn nth_and_nth_back_match_nested_loops() {
+        for order in [Order::RowMajor, Order::ColumnMajor] {
+            let expected = nested_loops(order);
+            for n in 0..=expected.len() {
+                assert_eq!(indices(order).nth(n).as_ref(), expected.get(n));
+                let back = expected.len().checked_sub(n + 1).map(|i| &expected[i]);
+                assert_eq!(indices(order).nth_back(n).as_ref(), back);
+            }
+            let mut iter = indices(order);
+            assert_eq!(iter.nth(5).as_ref(), Some(&expected[5]));
+            assert_eq!(iter.nth_back(3).as_ref(), Some(&expected[20]));
+            assert_eq!(iter.next_back().as_ref(), Some(&expected[19]));
+            assert_eq!(iter.next().as_ref(), Some(&expected[6]));
+            assert_eq!(iter.len(), 12);
+            assert_eq!(iter.collect::<Vec<_>>(), &expected[7..19]);
+        }
+    }
+
+    #[test]
+    fn len_counts_down_from_both_ends() {
+        let mut iter = indices(Order::RowMajor);
+        for remaining in (0..24).rev() {
+            if remaining % 2 == 0 {
+                iter.next();
+            } else {
+                iter.next_back();
+            }
+            assert_eq!(iter.len(), remaining);
+        }
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn fixed_rank_indices_are_patterns() {
+        let indices: Vec<_> = Dense::new([2, 2]).indices_in(Order::ColumnMajor).collect();
+        assert_eq!(indices, [(0, 0), (1, 0), (0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn empty_layouts_have_no_indices() {
+        let mut iter = DenseDyn::new(&[3, 0, 2]).indices();
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+}
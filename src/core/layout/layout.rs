@@ -1,9 +1,54 @@
-pub trait Layout {
-    /// For fixed-size dimension representations (e.g. `Ix2`), this should be
+use std::ops::Range;
+
+use super::{contiguous_extent, IndexBuf, IndexFromSlice, Indices, Order};
+
+/// A trait representing how an array is laid out, including:
+///     1. Shape
+///     2. Type of index, i.e., the type for array[index_type]
+///
+/// # Safety
+///
+/// Arrays read and write their elements through the offsets a layout gives, trusting it
+/// to describe its own memory. Implementors must guarantee that:
+///
+/// 1. Every index accepted by [`Layout::contains_index`] has a [`Layout::to_offset`]
+///    within [`Layout::memory_extent`], whenever the latter is `Some`.
+/// 2. [`Layout::has_unique_offsets`] returns `true` only if no two such indices share
+///    an offset.
+/// 3. [`Layout::is_standard_layout`] returns `true` only if those indices, in row-major
+///    order, map to exactly the offsets `0..size`.
+/// 4. If [`Layout::compacted`] returns `None`, any offset within the extent that no valid
+///    index maps to, such as padding, can be read whenever the elements can.
+pub unsafe trait Layout {
+    /// The type used to index into the array, with one entry per axis.
+    ///
+    /// See [`NdIndex`](super::NdIndex) for the types that can be used as indices.
+    type Indexer: ?Sized + AsRef<[usize]> + IndexFromSlice;
+
+    /// For fixed-size dimension representations (e.g. `Dense<2>`), this should be
     /// `Some(ndim)`, and for variable-size dimension representations (e.g.
-    /// `IxDyn`), this should be `None`.
+    /// `DenseDyn`), this should be `None`.
     const NDIM: Option<usize>;
 
+    /// Convert from a reference to the `Indexer` type into a
+    /// linear offset from the "origin" of the array: the element at index zero.
+    ///
+    /// The offset is signed, since a layout with negative strides places
+    /// elements before its origin.
+    ///
+    /// **Panics** if `i` is out of bounds for the array.
+    fn to_offset(&self, i: &Self::Indexer) -> isize;
+
+    /// Returns `true` if `i` is a valid index into the array, i.e. [`Layout::to_offset`]
+    /// would not panic.
+    ///
+    /// By default, a valid index has one entry per axis and is in bounds along each axis.
+    /// Layouts that store only some of their elements should also reject the others.
+    fn contains_index(&self, i: &Self::Indexer) -> bool {
+        let i = i.as_ref();
+        i.len() == self.ndim() && i.iter().zip(self.slice()).all(|(&ix, &len)| ix < len)
+    }
+
     /// Returns the number of dimensions (number of axes).
     fn ndim(&self) -> usize;
 
@@ -19,11 +64,131 @@ pub trait Layout {
             .try_fold(1_usize, |s, &a| s.checked_mul(a))
     }
 
+    /// Return the range of offsets, relative to the origin, that the elements occupy,
+    /// or `None` if it does not fit in an `isize`.
+    ///
+    /// The range may include offsets that no index maps to, such as padding, and is
+    /// empty only if there is nothing to store. By default, the elements occupy
+    /// `0..size`.
+    fn memory_extent(&self) -> Option<Range<isize>> {
+        contiguous_extent(self.size_checked())
+    }
+
+    /// Returns `true` if no two valid indices, as given by [`Layout::contains_index`],
+    /// map to the same offset.
+    ///
+    /// Layouts that cannot cheaply tell may return `false` for some that do not;
+    /// by default, every index has an offset of its own.
+    fn has_unique_offsets(&self) -> bool {
+        true
+    }
+
+    /// Returns `true` if the layout is meant to give every index an offset of its own, so
+    /// that two indices sharing one is a mistake rather than the point.
+    ///
+    /// Checked constructors reject aliasing only in layouts that expect unique offsets;
+    /// see [`validate_unaliased`](super::validate_unaliased). Anything that hands out
+    /// mutable references to many elements at once must still check
+    /// [`Layout::has_unique_offsets`]. By default, layouts expect unique offsets.
+    fn expects_unique_offsets(&self) -> bool {
+        true
+    }
+
+    /// Returns `true` if the layout can have its origin at `origin`, with elements of type `E`.
+    ///
+    /// Layouts that promise aligned rows override this; checked constructors call it
+    /// through [`validate_alignment`](super::validate_alignment). By default, any pointer
+    /// will do.
+    fn is_aligned_for<E>(&self, _origin: *const E) -> bool {
+        true
+    }
+
+    /// The alignment, in bytes, that [`Layout::is_aligned_for`] asks of the origin.
+    ///
+    /// Allocations made for the layout leave this much room to align the origin. By
+    /// default, the element type's own alignment is enough, so this is `1`.
+    fn origin_alignment(&self) -> usize {
+        1
+    }
+
+    /// Returns `true` if the elements are laid out contiguously in row-major
+    /// order, starting from the "origin" of the array.
+    ///
+    /// Layouts that cannot promise this should keep the default, which returns `false`.
+    fn is_standard_layout(&self) -> bool {
+        false
+    }
+
+    /// Return a layout of the same shape and valid indices, whose offsets leave no gaps
+    /// in its extent, for copying just the elements into a new allocation.
+    ///
+    /// Layouts that can leave gaps between elements another array may own, as strides
+    /// can, must return one. Layouts whose only gaps are part of their own storage, such as
+    /// padding, keep the default, which returns `None`; those gaps are then copied too.
+    fn compacted(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
     #[doc(hidden)]
     fn slice(&self) -> &[usize];
 
-    #[doc(hidden)]
-    fn slice_mut(&mut self) -> &mut [usize];
+    /// Call `f` with the offset of every valid index, as given by [`Layout::contains_index`],
+    /// in row-major order.
+    ///
+    /// Indices that share an element each pass its offset.
+    fn for_each_offset(&self, mut f: impl FnMut(isize)) {
+        let shape = self.slice();
+        if shape.contains(&0) {
+            return;
+        }
+        let mut index = vec![0; shape.len()];
+        loop {
+            Self::Indexer::with_slice(&index, |ix| {
+                if self.contains_index(ix) {
+                    f(self.to_offset(ix));
+                }
+            });
+            // Step to the next index, or stop once the first axis wraps around
+            let wrapped = index.iter_mut().zip(shape).rev().all(|(ix, &len)| {
+                *ix += 1;
+                if *ix == len {
+                    *ix = 0;
+                    true
+                } else {
+                    false
+                }
+            });
+            if wrapped {
+                return;
+            }
+        }
+    }
+
+    /// Return an iterator over every index of the layout, in row-major order.
+    ///
+    /// The iterator covers the whole shape, including any index that
+    /// [`Layout::contains_index`] rejects, such as one outside the band of a
+    /// [`Banded`](super::Banded) layout; [`Layout::to_offset`] panics on those, so
+    /// layouts that store only some of their elements need callers to filter them out.
+    fn indices(&self) -> Indices<Self::Indexer>
+    where
+        Self::Indexer: IndexBuf,
+    {
+        self.indices_in(Order::RowMajor)
+    }
+
+    /// Return an iterator over every index of the layout, in the given order.
+    ///
+    /// See [`Layout::indices`] for the indices that need filtering out.
+    fn indices_in(&self, order: Order) -> Indices<Self::Indexer>
+    where
+        Self::Indexer: IndexBuf,
+    {
+        Indices::new(self.slice(), order)
+    }
 
     // /// Borrow as a read-only array view.
     // fn as_array_view(&self) -> ArrayView1<'_, Ix>
@@ -42,141 +207,6 @@ pub trait Layout {
         self.slice() == rhs.slice()
     }
 
-    // /// Returns the strides for a standard layout array with the given shape.
-    // ///
-    // /// If the array is non-empty, the strides result in contiguous layout; if
-    // /// the array is empty, the strides are all zeros.
-    // #[doc(hidden)]
-    // fn default_strides(&self) -> Self {
-    //     // Compute default array strides
-    //     // Shape (a, b, c) => Give strides (b * c, c, 1)
-    //     let mut strides = Self::zeros(self.ndim());
-    //     // For empty arrays, use all zero strides.
-    //     if self.slice().iter().all(|&d| d != 0) {
-    //         let mut it = strides.slice_mut().iter_mut().rev();
-    //         // Set first element to 1
-    //         if let Some(rs) = it.next() {
-    //             *rs = 1;
-    //         }
-    //         let mut cum_prod = 1;
-    //         for (rs, dim) in it.zip(self.slice().iter().rev()) {
-    //             cum_prod *= *dim;
-    //             *rs = cum_prod;
-    //         }
-    //     }
-    //     strides
-    // }
-
-    // /// Returns the strides for a Fortran layout array with the given shape.
-    // ///
-    // /// If the array is non-empty, the strides result in contiguous layout; if
-    // /// the array is empty, the strides are all zeros.
-    // #[doc(hidden)]
-    // fn fortran_strides(&self) -> Self
-    // {
-    //     // Compute fortran array strides
-    //     // Shape (a, b, c) => Give strides (1, a, a * b)
-    //     let mut strides = Self::zeros(self.ndim());
-    //     // For empty arrays, use all zero strides.
-    //     if self.slice().iter().all(|&d| d != 0) {
-    //         let mut it = strides.slice_mut().iter_mut();
-    //         // Set first element to 1
-    //         if let Some(rs) = it.next() {
-    //             *rs = 1;
-    //         }
-    //         let mut cum_prod = 1;
-    //         for (rs, dim) in it.zip(self.slice()) {
-    //             cum_prod *= *dim;
-    //             *rs = cum_prod;
-    //         }
-    //     }
-    //     strides
-    // }
-
-    // /// Creates a dimension of all zeros with the specified ndim.
-    // ///
-    // /// This method is useful for generalizing over fixed-size and
-    // /// variable-size dimension representations.
-    // ///
-    // /// **Panics** if `Self` has a fixed size that is not `ndim`.
-    // fn zeros(ndim: usize) -> Self;
-
-    // #[doc(hidden)]
-    // #[inline]
-    // fn first_index(&self) -> Option<Self>
-    // {
-    //     for ax in self.slice().iter() {
-    //         if *ax == 0 {
-    //             return None;
-    //         }
-    //     }
-    //     Some(Self::zeros(self.ndim()))
-    // }
-
-    // #[doc(hidden)]
-    // /// Iteration -- Use self as size, and return next index after `index`
-    // /// or None if there are no more.
-    // // FIXME: use &Self for index or even &mut?
-    // #[inline]
-    // fn next_for(&self, index: Self) -> Option<Self>
-    // {
-    //     let mut index = index;
-    //     let mut done = false;
-    //     for (&dim, ix) in zip(self.slice(), index.slice_mut()).rev() {
-    //         *ix += 1;
-    //         if *ix == dim {
-    //             *ix = 0;
-    //         } else {
-    //             done = true;
-    //             break;
-    //         }
-    //     }
-    //     if done {
-    //         Some(index)
-    //     } else {
-    //         None
-    //     }
-    // }
-
-    // #[doc(hidden)]
-    // /// Iteration -- Use self as size, and create the next index after `index`
-    // /// Return false if iteration is done
-    // ///
-    // /// Next in f-order
-    // #[inline]
-    // fn next_for_f(&self, index: &mut Self) -> bool
-    // {
-    //     let mut end_iteration = true;
-    //     for (&dim, ix) in zip(self.slice(), index.slice_mut()) {
-    //         *ix += 1;
-    //         if *ix == dim {
-    //             *ix = 0;
-    //         } else {
-    //             end_iteration = false;
-    //             break;
-    //         }
-    //     }
-    //     !end_iteration
-    // }
-
-    // /// Returns `true` iff `strides1` and `strides2` are equivalent for the
-    // /// shape `self`.
-    // ///
-    // /// The strides are equivalent if, for each axis with length > 1, the
-    // /// strides are equal.
-    // ///
-    // /// Note: Returns `false` if any of the ndims don't match.
-    // #[doc(hidden)]
-    // fn strides_equivalent<D>(&self, strides1: &Self, strides2: &D) -> bool
-    // where D: Dimension
-    // {
-    //     let shape_ndim = self.ndim();
-    //     shape_ndim == strides1.ndim()
-    //         && shape_ndim == strides2.ndim()
-    //         && izip!(self.slice(), strides1.slice(), strides2.slice())
-    //             .all(|(&d, &s1, &s2)| d <= 1 || s1 as isize == s2 as isize)
-    // }
-
     // #[doc(hidden)]
     // /// Return stride offset for index.
     // fn stride_offset(index: &Self, strides: &Self) -> isize
@@ -212,105 +242,6 @@ pub trait Layout {
     //     self.slice_mut()[nd - 1] = i;
     // }
 
-    // #[doc(hidden)]
-    // fn is_contiguous(dim: &Self, strides: &Self) -> bool
-    // {
-    //     let defaults = dim.default_strides();
-    //     if strides.equal(&defaults) {
-    //         return true;
-    //     }
-    //     if dim.ndim() == 1 {
-    //         // fast case for ndim == 1:
-    //         // Either we have length <= 1, then stride is arbitrary,
-    //         // or we have stride == 1 or stride == -1, but +1 case is already handled above.
-    //         dim[0] <= 1 || strides[0] as isize == -1
-    //     } else {
-    //         let order = strides._fastest_varying_stride_order();
-    //         let strides = strides.slice();
-
-    //         let dim_slice = dim.slice();
-    //         let mut cstride = 1;
-    //         for &i in order.slice() {
-    //             // a dimension of length 1 can have unequal strides
-    //             if dim_slice[i] != 1 && (strides[i] as isize).unsigned_abs() != cstride {
-    //                 return false;
-    //             }
-    //             cstride *= dim_slice[i];
-    //         }
-    //         true
-    //     }
-    // }
-
-    // /// Return the axis ordering corresponding to the fastest variation
-    // /// (in ascending order).
-    // ///
-    // /// Assumes that no stride value appears twice.
-    // #[doc(hidden)]
-    // fn _fastest_varying_stride_order(&self) -> Self
-    // {
-    //     let mut indices = self.clone();
-    //     for (i, elt) in enumerate(indices.slice_mut()) {
-    //         *elt = i;
-    //     }
-    //     let strides = self.slice();
-    //     indices
-    //         .slice_mut()
-    //         .sort_by_key(|&i| (strides[i] as isize).abs());
-    //     indices
-    // }
-
-    // /// Compute the minimum stride axis (absolute value), under the constraint
-    // /// that the length of the axis is > 1;
-    // #[doc(hidden)]
-    // fn min_stride_axis(&self, strides: &Self) -> Axis
-    // {
-    //     let n = match self.ndim() {
-    //         0 => panic!("min_stride_axis: Array must have ndim > 0"),
-    //         1 => return Axis(0),
-    //         n => n,
-    //     };
-    //     axes_of(self, strides)
-    //         .rev()
-    //         .min_by_key(|ax| ax.stride.abs())
-    //         .map_or(Axis(n - 1), |ax| ax.axis)
-    // }
-
-    // /// Compute the maximum stride axis (absolute value), under the constraint
-    // /// that the length of the axis is > 1;
-    // #[doc(hidden)]
-    // fn max_stride_axis(&self, strides: &Self) -> Axis
-    // {
-    //     match self.ndim() {
-    //         0 => panic!("max_stride_axis: Array must have ndim > 0"),
-    //         1 => return Axis(0),
-    //         _ => {}
-    //     }
-    //     axes_of(self, strides)
-    //         .filter(|ax| ax.len > 1)
-    //         .max_by_key(|ax| ax.stride.abs())
-    //         .map_or(Axis(0), |ax| ax.axis)
-    // }
-
-    // /// Convert the dimensional into a dynamic dimensional (IxDyn).
-    // fn into_dyn(self) -> IxDyn
-    // {
-    //     IxDyn(self.slice())
-    // }
-
-    // #[doc(hidden)]
-    // fn from_dimension<D2: Dimension>(d: &D2) -> Option<Self>
-    // {
-    //     let mut s = Self::default();
-    //     if s.ndim() == d.ndim() {
-    //         for i in 0..d.ndim() {
-    //             s[i] = d[i];
-    //         }
-    //         Some(s)
-    //     } else {
-    //         None
-    //     }
-    // }
-
     // #[doc(hidden)]
     // fn insert_axis(&self, axis: Axis) -> Self::Larger;
 
@@ -0,0 +1,209 @@
+//! Morton (Z-order) layouts, which lay elements out along a space-filling curve.
+//!
+//! An element's offset is found by interleaving the bits of its index along each axis,
+//! with the last axis in the lowest bit. Nearby indices therefore tend to have nearby
+//! offsets, whichever axis they differ along.
+//!
+//! Each axis is padded to the next power of two, and an axis stops contributing bits once
+//! its own bits run out. The layout is dense when every axis length is a power of two;
+//! otherwise some offsets below [`Morton::padded_size`] belong to no index.
+
+use std::ops::Range;
+
+use super::{contiguous_extent, Layout};
+
+/// A layout that orders elements along a Morton (Z-order) curve.
+///
+/// See the [module documentation](self) for how offsets are computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Morton<const N: usize> {
+    shape: [usize; N],
+    /// The number of bits needed to index each axis, once padded to a power of two.
+    bits: [u32; N],
+}
+
+/// A two-dimensional Morton layout.
+pub type Morton2 = Morton<2>;
+
+/// A three-dimensional Morton layout.
+pub type Morton3 = Morton<3>;
+
+impl<const N: usize> Morton<N> {
+    /// Create a Morton layout with the given shape.
+    ///
+    /// **Panics** if the padded layout would need more than `usize::BITS` bits of offset.
+    pub fn new(shape: [usize; N]) -> Self {
+        let bits = shape.map(|len| len.next_power_of_two().trailing_zeros());
+        assert!(
+            bits.iter().sum::<u32>() < usize::BITS,
+            "the shape {shape:?} is too large for a Morton layout"
+        );
+        Morton { shape, bits }
+    }
+
+    /// The length of each axis.
+    pub fn shape(&self) -> &[usize; N] {
+        &self.shape
+    }
+
+    /// The number of elements the layout spans, including padding.
+    ///
+    /// This is the product of the axis lengths, each rounded up to a power of two,
+    /// or zero if the layout is empty.
+    pub fn padded_size(&self) -> usize {
+        if self.shape.contains(&0) {
+            0
+        } else {
+            1 << self.bits.iter().sum::<u32>()
+        }
+    }
+
+    /// Find the index at `offset`, the inverse of [`Layout::to_offset`].
+    ///
+    /// Returns `None` if `offset` falls on padding, or past the end of the layout.
+    pub fn from_offset(&self, offset: usize) -> Option<[usize; N]> {
+        if offset >= self.padded_size() {
+            return None;
+        }
+        let mut index = [0; N];
+        let mut bit = 0;
+        self.for_each_bit(|axis, level| {
+            index[axis] |= ((offset >> bit) & 1) << level;
+            bit += 1;
+        });
+        let in_bounds = index.iter().zip(&self.shape).all(|(&ix, &len)| ix < len);
+        in_bounds.then_some(index)
+    }
+
+    /// Return the indices of the layout in the order they are stored.
+    pub fn z_order(&self) -> ZOrder<N> {
+        ZOrder {
+            layout: *self,
+            offset: 0,
+        }
+    }
+
+    /// Call `f(axis, level)` for each bit of the interleaved offset, from lowest to highest,
+    /// where the bit is bit `level` of the index along `axis`.
+    fn for_each_bit(&self, mut f: impl FnMut(usize, u32)) {
+        let max_bits = self.bits.iter().copied().max().unwrap_or(0);
+        for level in 0..max_bits {
+            for axis in (0..N).rev() {
+                if level < self.bits[axis] {
+                    f(axis, level);
+                }
+            }
+        }
+    }
+}
+
+// Offsets interleave the bits of in-bounds indices, so they stay within the padded extent.
+unsafe impl<const N: usize> Layout for Morton<N> {
+    type Indexer = [usize; N];
+
+    const NDIM: Option<usize> = Some(N);
+
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        for (axis, (&ix, &len)) in i.iter().zip(&self.shape).enumerate() {
+            assert!(
+                ix < len,
+                "index {ix} is out of bounds for axis {axis} with length {len}"
+            );
+        }
+        let mut offset = 0_usize;
+        let mut bit = 0;
+        self.for_each_bit(|axis, level| {
+            offset |= ((i[axis] >> level) & 1) << bit;
+            bit += 1;
+        });
+        offset as isize
+    }
+
+    fn ndim(&self) -> usize {
+        N
+    }
+
+    /// Every offset up to [`Morton::padded_size`], including those that fall on padding.
+    fn memory_extent(&self) -> Option<Range<isize>> {
+        contiguous_extent(Some(self.padded_size()))
+    }
+
+    /// Returns `true` if at most one axis is longer than one, so that the curve
+    /// simply walks along that axis.
+    fn is_standard_layout(&self) -> bool {
+        self.shape.contains(&0) || self.shape.iter().filter(|&&len| len > 1).count() <= 1
+    }
+
+    fn slice(&self) -> &[usize] {
+        &self.shape
+    }
+}
+
+/// An iterator over the indices of a [`Morton`] layout, in the order they are stored.
+///
+/// Created by [`Morton::z_order`].
+#[derive(Debug, Clone)]
+pub struct ZOrder<const N: usize> {
+    layout: Morton<N>,
+    offset: usize,
+}
+
+impl<const N: usize> Iterator for ZOrder<N> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Skip over the padding until the next offset that belongs to an index
+        while self.offset < self.layout.padded_size() {
+            let index = self.layout.from_offset(self.offset);
+            self.offset += 1;
+            if index.is_some() {
+                return index;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::layout::testing::sorted_offsets;
+
+    #[test]
+    fn offsets_are_a_bijection_for_powers_of_two() {
+        let layout = Morton::new([4, 8]);
+        assert_eq!(sorted_offsets(&layout), (0..32).collect::<Vec<_>>());
+        let layout = Morton::new([2, 4, 2]);
+        assert_eq!(sorted_offsets(&layout), (0..16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn offsets_are_unique_within_padding() {
+        let layout = Morton::new([3, 5]);
+        let mut offsets = sorted_offsets(&layout);
+        offsets.dedup();
+        assert_eq!(offsets.len(), 15);
+        assert!(offsets.iter().all(|&offset| offset < 32));
+        assert_eq!(layout.memory_extent(), Some(0..32));
+    }
+
+    #[test]
+    fn from_offset_inverts_to_offset() {
+        let layout = Morton::new([3, 5, 2]);
+        for offset in 0..layout.padded_size() {
+            if let Some(index) = layout.from_offset(offset) {
+                assert_eq!(layout.to_offset(&index), offset as isize);
+            }
+        }
+        assert_eq!(layout.z_order().count(), 30);
+        assert_eq!(layout.from_offset(layout.padded_size()), None);
+    }
+
+    #[test]
+    fn interleaves_with_the_last_axis_lowest() {
+        let layout = Morton::new([4, 4]);
+        assert_eq!(layout.to_offset(&[0, 1]), 1);
+        assert_eq!(layout.to_offset(&[1, 0]), 2);
+        assert_eq!(layout.to_offset(&[3, 3]), 15);
+    }
+}
@@ -0,0 +1,251 @@
+//! Layouts with named axes, and selecting axes by name or position.
+
+use std::ops::Range;
+
+use crate::core::{ErrorKind, ShapeError};
+
+use super::{AddAxis, IndexAxis, InsertAxis, Layout, PermuteAxes, RemoveAxis};
+
+/// A layout whose axes may each carry a name, such as `"time"` or `"lat"`.
+///
+/// Named axes can be selected by name wherever an [`AxisSelector`] is accepted,
+/// and their names follow them through [`RemoveAxis`], [`AddAxis`], [`IndexAxis`],
+/// [`InsertAxis`], and [`PermuteAxes`].
+/// No two axes may share a name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Named<L> {
+    layout: L,
+    names: Vec<Option<String>>,
+}
+
+impl<L: Layout> Named<L> {
+    /// Wrap `layout`, naming each of its axes in turn.
+    ///
+    /// Fails if there is not exactly one name per axis, or if any name is repeated.
+    pub fn new<S: Into<String>>(
+        layout: L,
+        names: impl IntoIterator<Item = S>,
+    ) -> Result<Self, ShapeError> {
+        let names: Vec<_> = names.into_iter().map(|name| Some(name.into())).collect();
+        if names.len() != layout.ndim() {
+            return Err(ShapeError::from_kind(ErrorKind::IncompatibleRank {
+                expected: layout.ndim(),
+                found: names.len(),
+            }));
+        }
+        let mut named = Named::unnamed(layout);
+        for (axis, name) in names.into_iter().enumerate() {
+            named.set_name(axis, name)?;
+        }
+        Ok(named)
+    }
+
+    /// Wrap `layout`, leaving all of its axes unnamed.
+    pub fn unnamed(layout: L) -> Self {
+        let names = vec![None; layout.ndim()];
+        Named { layout, names }
+    }
+
+    /// Name `axis`, replacing any name it already has.
+    ///
+    /// Fails if `axis` is out of bounds, or if another axis already has this name.
+    pub fn with_axis_name(
+        mut self,
+        axis: usize,
+        name: impl Into<String>,
+    ) -> Result<Self, ShapeError> {
+        axis.select(&self)?;
+        self.set_name(axis, Some(name.into()))?;
+        Ok(self)
+    }
+
+    /// Return the underlying layout.
+    pub fn layout(&self) -> &L {
+        &self.layout
+    }
+
+    /// Unwrap the underlying layout, discarding the names.
+    pub fn into_layout(self) -> L {
+        self.layout
+    }
+
+    /// Return the name of each axis, or `None` for unnamed axes.
+    pub fn names(&self) -> impl Iterator<Item = Option<&str>> {
+        self.names.iter().map(Option::as_deref)
+    }
+
+    /// Return the name of `axis`, or `None` if it is unnamed or out of bounds.
+    pub fn axis_name(&self, axis: usize) -> Option<&str> {
+        self.names.get(axis)?.as_deref()
+    }
+
+    /// Find the axis called `name`.
+    pub fn axis(&self, name: &str) -> Result<usize, ShapeError> {
+        self.names
+            .iter()
+            .position(|n| n.as_deref() == Some(name))
+            .ok_or_else(|| {
+                ShapeError::from_kind(ErrorKind::UnknownAxisName {
+                    name: name.to_owned(),
+                    names: self.names.clone(),
+                })
+            })
+    }
+
+    fn set_name(&mut self, axis: usize, name: Option<String>) -> Result<(), ShapeError> {
+        if let Some(name) = &name {
+            if self.axis(name).is_ok_and(|other| other != axis) {
+                return Err(ShapeError::from_kind(ErrorKind::DuplicateAxisName {
+                    name: name.clone(),
+                }));
+            }
+        }
+        self.names[axis] = name;
+        Ok(())
+    }
+}
+
+// Names do not move any element; every answer comes from the wrapped layout.
+unsafe impl<L: Layout> Layout for Named<L> {
+    type Indexer = L::Indexer;
+
+    const NDIM: Option<usize> = L::NDIM;
+
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        self.layout.to_offset(i)
+    }
+
+    fn contains_index(&self, i: &Self::Indexer) -> bool {
+        self.layout.contains_index(i)
+    }
+
+    fn ndim(&self) -> usize {
+        self.layout.ndim()
+    }
+
+    fn size(&self) -> usize {
+        self.layout.size()
+    }
+
+    fn memory_extent(&self) -> Option<Range<isize>> {
+        self.layout.memory_extent()
+    }
+
+    fn has_unique_offsets(&self) -> bool {
+        self.layout.has_unique_offsets()
+    }
+
+    fn expects_unique_offsets(&self) -> bool {
+        self.layout.expects_unique_offsets()
+    }
+
+    fn is_aligned_for<E>(&self, origin: *const E) -> bool {
+        self.layout.is_aligned_for(origin)
+    }
+
+    fn origin_alignment(&self) -> usize {
+        self.layout.origin_alignment()
+    }
+
+    fn is_standard_layout(&self) -> bool {
+        self.layout.is_standard_layout()
+    }
+
+    fn compacted(&self) -> Option<Self> {
+        let layout = self.layout.compacted()?;
+        Some(Named {
+            layout,
+            names: self.names.clone(),
+        })
+    }
+
+    fn slice(&self) -> &[usize] {
+        self.layout.slice()
+    }
+}
+
+impl<L: RemoveAxis> RemoveAxis for Named<L> {
+    type Smaller = Named<L::Smaller>;
+
+    fn remove_axis(&self, axis: usize) -> Self::Smaller {
+        let layout = self.layout.remove_axis(axis);
+        let mut names = self.names.clone();
+        names.remove(axis.min(names.len() - 1));
+        Named { layout, names }
+    }
+}
+
+impl<L: AddAxis> AddAxis for Named<L> {
+    type Larger = Named<L::Larger>;
+
+    /// The new axis is unnamed; see [`Named::with_axis_name`].
+    fn add_axis(&self, axis: usize, length: usize) -> Self::Larger {
+        let layout = self.layout.add_axis(axis, length);
+        let mut names = self.names.clone();
+        names.insert(axis.min(names.len()), None);
+        Named { layout, names }
+    }
+}
+
+impl<L: IndexAxis> IndexAxis for Named<L> {
+    type Smaller = Named<L::Smaller>;
+
+    fn index_axis(&self, axis: usize, index: usize) -> (Self::Smaller, isize) {
+        let (layout, offset) = self.layout.index_axis(axis, index);
+        let mut names = self.names.clone();
+        names.remove(axis);
+        (Named { layout, names }, offset)
+    }
+}
+
+impl<L: InsertAxis> InsertAxis for Named<L> {
+    type Larger = Named<L::Larger>;
+
+    /// The new axis is unnamed; see [`Named::with_axis_name`].
+    fn insert_axis(&self, axis: usize) -> Self::Larger {
+        let layout = self.layout.insert_axis(axis);
+        let mut names = self.names.clone();
+        names.insert(axis, None);
+        Named { layout, names }
+    }
+}
+
+impl<L: PermuteAxes> PermuteAxes for Named<L> {
+    type Permuted = Named<L::Permuted>;
+
+    fn permuted_axes(&self, axes: &[usize]) -> Self::Permuted {
+        let layout = self.layout.permuted_axes(axes);
+        let names = axes.iter().map(|&axis| self.names[axis].clone()).collect();
+        Named { layout, names }
+    }
+}
+
+/// A way of picking out one axis of a layout.
+///
+/// Every layout accepts an axis's position, as a `usize`; [`Named`] layouts also
+/// accept its name, as a `&str`.
+pub trait AxisSelector<L: Layout> {
+    /// Return the position of the selected axis.
+    ///
+    /// Fails if `layout` has no such axis.
+    fn select(&self, layout: &L) -> Result<usize, ShapeError>;
+}
+
+impl<L: Layout> AxisSelector<L> for usize {
+    fn select(&self, layout: &L) -> Result<usize, ShapeError> {
+        if *self < layout.ndim() {
+            Ok(*self)
+        } else {
+            Err(ShapeError::from_kind(ErrorKind::AxisOutOfBounds {
+                axis: *self,
+                ndim: layout.ndim(),
+            }))
+        }
+    }
+}
+
+impl<L: Layout> AxisSelector<Named<L>> for &str {
+    fn select(&self, layout: &Named<L>) -> Result<usize, ShapeError> {
+        layout.axis(self)
+    }
+}
@@ -0,0 +1,321 @@
+//! Memory order policies, modeled on C++23 `mdspan`'s `layout_right` and `layout_left`.
+
+use crate::core::{ErrorKind, ShapeError};
+
+use super::{
+    add_axis_into, assert_insert_axis, Dense, IndexAxis, InsertAxis, Layout, PermuteAxes, Strided,
+};
+
+/// The order in which the elements of a contiguous array are laid out in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// Row-major ("C") order: the last axis varies fastest.
+    RowMajor,
+    /// Column-major ("Fortran") order: the first axis varies fastest.
+    ColumnMajor,
+}
+
+impl Order {
+    /// "C" is an alias for row-major order.
+    pub const C: Order = Order::RowMajor;
+
+    /// "F" is an alias for column-major order.
+    pub const F: Order = Order::ColumnMajor;
+
+    /// Return the opposite order.
+    pub fn transpose(self) -> Order {
+        match self {
+            Order::RowMajor => Order::ColumnMajor,
+            Order::ColumnMajor => Order::RowMajor,
+        }
+    }
+}
+
+/// A contiguous, row-major layout, like `mdspan`'s `layout_right`.
+///
+/// [`Dense`] is already row-major; this alias names it as the counterpart to [`ColMajor`].
+pub type RowMajor<const N: usize> = Dense<N>;
+
+/// A contiguous, column-major layout with a fixed number of dimensions,
+/// like `mdspan`'s `layout_left`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColMajor<const N: usize>(pub(super) [usize; N]);
+
+impl<const N: usize> ColMajor<N> {
+    /// Create a column-major layout with the given shape.
+    pub fn new(shape: [usize; N]) -> Self {
+        ColMajor(shape)
+    }
+
+    /// Return the strides implied by the shape.
+    pub fn strides(&self) -> [isize; N] {
+        let mut strides = [0; N];
+        contiguous_strides(&self.0, Order::ColumnMajor, &mut strides);
+        strides
+    }
+
+    /// Reverse the order of the axes, giving a row-major layout over the same data.
+    pub fn reversed_axes(mut self) -> Dense<N> {
+        self.0.reverse();
+        Dense(self.0)
+    }
+}
+
+// In-bounds indices map one-to-one onto `0..size`, in column-major order.
+unsafe impl<const N: usize> Layout for ColMajor<N> {
+    type Indexer = [usize; N];
+
+    const NDIM: Option<usize> = Some(N);
+
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        contiguous_offset(&self.0, i, Order::ColumnMajor) as isize
+    }
+
+    fn ndim(&self) -> usize {
+        N
+    }
+
+    fn is_standard_layout(&self) -> bool {
+        is_order_agnostic(&self.0)
+    }
+
+    fn slice(&self) -> &[usize] {
+        &self.0
+    }
+}
+
+impl<const N: usize> IndexAxis for ColMajor<N>
+where
+    Strided<N>: IndexAxis,
+{
+    type Smaller = <Strided<N> as IndexAxis>::Smaller;
+
+    /// The remaining elements are only contiguous when indexing the last axis, so the
+    /// result is always strided.
+    fn index_axis(&self, axis: usize, index: usize) -> (Self::Smaller, isize) {
+        Strided::from(*self).index_axis(axis, index)
+    }
+}
+
+impl<const N: usize> PermuteAxes for ColMajor<N> {
+    type Permuted = Strided<N>;
+
+    /// See [`Dense::permuted_axes`].
+    fn permuted_axes(&self, axes: &[usize]) -> Self::Permuted {
+        Strided::from(*self).permuted_axes(axes)
+    }
+}
+
+macro_rules! impl_insert_axis {
+    ($n:literal, $larger:literal) => {
+        impl InsertAxis for ColMajor<$n> {
+            type Larger = ColMajor<$larger>;
+
+            fn insert_axis(&self, axis: usize) -> Self::Larger {
+                assert_insert_axis(axis, $n);
+                let mut larger = [0; $larger];
+                add_axis_into(&self.0, axis, 1, &mut larger);
+                ColMajor(larger)
+            }
+        }
+    };
+}
+
+impl_insert_axis!(0, 1);
+impl_insert_axis!(1, 2);
+impl_insert_axis!(2, 3);
+impl_insert_axis!(3, 4);
+impl_insert_axis!(4, 5);
+impl_insert_axis!(5, 6);
+
+impl<const N: usize> From<Dense<N>> for Strided<N> {
+    fn from(value: Dense<N>) -> Self {
+        Strided::new(value.0, value.strides())
+    }
+}
+
+impl<const N: usize> From<ColMajor<N>> for Strided<N> {
+    fn from(value: ColMajor<N>) -> Self {
+        Strided::new(value.0, value.strides())
+    }
+}
+
+impl<const N: usize> TryFrom<ColMajor<N>> for Dense<N> {
+    type Error = ShapeError;
+
+    /// Succeeds only when both orders put every element at the same offset,
+    /// i.e. the layout is empty or at most one axis is longer than one.
+    fn try_from(value: ColMajor<N>) -> Result<Self, Self::Error> {
+        if is_order_agnostic(&value.0) {
+            Ok(Dense(value.0))
+        } else {
+            Err(ShapeError::from_kind(ErrorKind::IncompatibleLayout))
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<Dense<N>> for ColMajor<N> {
+    type Error = ShapeError;
+
+    /// Succeeds only when both orders put every element at the same offset,
+    /// i.e. the layout is empty or at most one axis is longer than one.
+    fn try_from(value: Dense<N>) -> Result<Self, Self::Error> {
+        if is_order_agnostic(&value.0) {
+            Ok(ColMajor(value.0))
+        } else {
+            Err(ShapeError::from_kind(ErrorKind::IncompatibleLayout))
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<Strided<N>> for Dense<N> {
+    type Error = ShapeError;
+
+    /// Succeeds only when the strides are equivalent to row-major strides.
+    fn try_from(value: Strided<N>) -> Result<Self, Self::Error> {
+        let dense = Dense(value.shape);
+        if strides_equivalent(&value.shape, &value.strides, &dense.strides()) {
+            Ok(dense)
+        } else {
+            Err(ShapeError::from_kind(ErrorKind::IncompatibleLayout))
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<Strided<N>> for ColMajor<N> {
+    type Error = ShapeError;
+
+    /// Succeeds only when the strides are equivalent to column-major strides.
+    fn try_from(value: Strided<N>) -> Result<Self, Self::Error> {
+        let col = ColMajor(value.shape);
+        if strides_equivalent(&value.shape, &value.strides, &col.strides()) {
+            Ok(col)
+        } else {
+            Err(ShapeError::from_kind(ErrorKind::IncompatibleLayout))
+        }
+    }
+}
+
+/// Write the strides of a contiguous array with the given `shape` and `order` into `strides`.
+///
+/// If the array is non-empty, the strides result in contiguous layout; if
+/// the array is empty, the strides are all zeros.
+pub(super) fn contiguous_strides(shape: &[usize], order: Order, strides: &mut [isize]) {
+    strides.fill(0);
+    // For empty arrays, use all zero strides.
+    if shape.contains(&0) {
+        return;
+    }
+    let mut cum_prod = 1;
+    let mut fill = |(s, &d): (&mut isize, &usize)| {
+        *s = cum_prod as isize;
+        cum_prod *= d;
+    };
+    match order {
+        // Shape (a, b, c) => Give strides (b * c, c, 1)
+        Order::RowMajor => strides.iter_mut().zip(shape).rev().for_each(&mut fill),
+        // Shape (a, b, c) => Give strides (1, a, a * b)
+        Order::ColumnMajor => strides.iter_mut().zip(shape).for_each(&mut fill),
+    }
+}
+
+/// Compute the offset of `index` into a contiguous array of the given `shape` and `order`.
+///
+/// **Panics** if `index` does not have one entry per axis, or if any entry is out of bounds.
+pub(super) fn contiguous_offset(shape: &[usize], index: &[usize], order: Order) -> usize {
+    assert_eq!(
+        index.len(),
+        shape.len(),
+        "index has {} entries, but the layout has {} dimensions",
+        index.len(),
+        shape.len()
+    );
+    let mut offset = 0;
+    let mut accumulate = |(axis, (&ix, &len)): (usize, (&usize, &usize))| {
+        assert!(
+            ix < len,
+            "index {ix} is out of bounds for axis {axis} with length {len}"
+        );
+        offset = offset * len + ix;
+    };
+    let axes = index.iter().zip(shape).enumerate();
+    match order {
+        Order::RowMajor => axes.for_each(&mut accumulate),
+        Order::ColumnMajor => axes.rev().for_each(&mut accumulate),
+    }
+    offset
+}
+
+/// Returns `true` iff `strides1` and `strides2` are equivalent for `shape`.
+///
+/// The strides are equivalent if the shape has no elements, or if, for each
+/// axis with length > 1, the strides are equal.
+pub(super) fn strides_equivalent(shape: &[usize], strides1: &[isize], strides2: &[isize]) -> bool {
+    shape.contains(&0)
+        || shape
+            .iter()
+            .zip(strides1.iter().zip(strides2))
+            .all(|(&d, (&s1, &s2))| d <= 1 || s1 == s2)
+}
+
+/// Whether row-major and column-major order agree on the offset of every element of `shape`.
+fn is_order_agnostic(shape: &[usize]) -> bool {
+    shape.contains(&0) || shape.iter().filter(|&&d| d > 1).count() <= 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_major_offsets_vary_fastest_along_the_first_axis() {
+        let layout = ColMajor::new([2, 3, 4]);
+        assert_eq!(layout.strides(), [1, 2, 6]);
+        let mut expected = 0;
+        for k in 0..4 {
+            for j in 0..3 {
+                for i in 0..2 {
+                    assert_eq!(layout.to_offset(&[i, j, k]), expected);
+                    expected += 1;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reversing_the_axes_switches_the_order() {
+        let layout = ColMajor::new([2, 3]);
+        let reversed = layout.reversed_axes();
+        assert_eq!(reversed, Dense::new([3, 2]));
+        assert_eq!(reversed.reversed_axes(), layout);
+        for (i, j) in (0..2).flat_map(|i| (0..3).map(move |j| (i, j))) {
+            assert_eq!(reversed.to_offset(&[j, i]), layout.to_offset(&[i, j]));
+        }
+    }
+
+    #[test]
+    fn only_order_agnostic_shapes_are_standard() {
+        assert!(!ColMajor::new([2, 3]).is_standard_layout());
+        assert!(ColMajor::new([1, 3]).is_standard_layout());
+        assert!(ColMajor::new([3, 0]).is_standard_layout());
+        assert_eq!(
+            Dense::try_from(ColMajor::new([1, 3])),
+            Ok(Dense::new([1, 3]))
+        );
+        let err = ColMajor::try_from(Dense::new([2, 3])).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::IncompatibleLayout);
+    }
+
+    #[test]
+    fn strided_layouts_convert_when_their_strides_agree() {
+        let strided = Strided::from(ColMajor::new([2, 3]));
+        assert_eq!(strided, Strided::new([2, 3], [1, 2]));
+        assert_eq!(ColMajor::try_from(strided), Ok(ColMajor::new([2, 3])));
+        assert!(Dense::try_from(strided).is_err());
+        // The stride of an axis of length one is never used
+        assert_eq!(
+            ColMajor::try_from(Strided::new([2, 1], [1, 7])),
+            Ok(ColMajor::new([2, 1]))
+        );
+    }
+}
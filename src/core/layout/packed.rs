@@ -0,0 +1,284 @@
+//! Packed triangular and symmetric matrix layouts, in LAPACK's packed storage format.
+//!
+//! Only one triangle of an `n` × `n` matrix is stored, in column-major order, taking
+//! `n * (n + 1) / 2` elements. With 0-based indices, element `(i, j)` of the stored
+//! triangle is at offset:
+//!
+//! - `i + j * (j + 1) / 2` for the upper triangle (`i <= j`), and
+//! - `i + j * (2 * n - j - 1) / 2` for the lower triangle (`j <= i`).
+
+use std::ops::Range;
+
+use super::{contiguous_extent, Layout};
+
+/// Which triangle of a square matrix is stored, like LAPACK's `UPLO` argument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Triangle {
+    /// The upper triangle, `i <= j`, including the diagonal.
+    Upper,
+    /// The lower triangle, `i >= j`, including the diagonal.
+    Lower,
+}
+
+impl Triangle {
+    /// Returns `true` if `(i, j)` is in the triangle.
+    pub fn contains(self, [i, j]: [usize; 2]) -> bool {
+        match self {
+            Triangle::Upper => i <= j,
+            Triangle::Lower => i >= j,
+        }
+    }
+
+    /// Return the other triangle.
+    pub fn transpose(self) -> Triangle {
+        match self {
+            Triangle::Upper => Triangle::Lower,
+            Triangle::Lower => Triangle::Upper,
+        }
+    }
+}
+
+/// A triangular matrix that stores only one triangle, in packed format.
+///
+/// Elements outside the stored triangle are known to be zero and have no offset:
+/// [`try_offset`](PackedTriangular::try_offset) returns `None` for them, while
+/// [`Layout::to_offset`] panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedTriangular {
+    shape: [usize; 2],
+    triangle: Triangle,
+}
+
+impl PackedTriangular {
+    /// Create a layout for an `n` × `n` matrix that stores `triangle`.
+    pub fn new(n: usize, triangle: Triangle) -> Self {
+        PackedTriangular {
+            shape: [n, n],
+            triangle,
+        }
+    }
+
+    /// Which triangle is stored.
+    pub fn triangle(&self) -> Triangle {
+        self.triangle
+    }
+
+    /// The number of elements that are actually stored.
+    pub fn packed_size(&self) -> usize {
+        packed_size(self.shape[0])
+    }
+
+    /// Return the offset of `index`, or `None` if it is outside the stored triangle
+    /// and so is known to be zero.
+    ///
+    /// **Panics** if `index` is out of bounds.
+    pub fn try_offset(&self, index: &[usize; 2]) -> Option<usize> {
+        check_bounds(self.shape[0], index);
+        self.triangle
+            .contains(*index)
+            .then(|| packed_offset(self.shape[0], self.triangle, *index))
+    }
+}
+
+// Only indices in the triangle are valid, and each has a slot of its own in the packed storage.
+unsafe impl Layout for PackedTriangular {
+    type Indexer = [usize; 2];
+
+    const NDIM: Option<usize> = Some(2);
+
+    /// **Panics** if `i` is outside the stored triangle; see
+    /// [`try_offset`](PackedTriangular::try_offset).
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        match self.try_offset(i) {
+            Some(offset) => offset as isize,
+            None => panic!(
+                "index {i:?} is outside the stored {:?} triangle",
+                self.triangle
+            ),
+        }
+    }
+
+    fn contains_index(&self, i: &Self::Indexer) -> bool {
+        i.iter().all(|&ix| ix < self.shape[0]) && self.triangle.contains(*i)
+    }
+
+    fn ndim(&self) -> usize {
+        2
+    }
+
+    fn memory_extent(&self) -> Option<Range<isize>> {
+        contiguous_extent(packed_size_checked(self.shape[0]))
+    }
+
+    fn slice(&self) -> &[usize] {
+        &self.shape
+    }
+}
+
+/// A symmetric matrix that stores only one triangle, in packed format.
+///
+/// `(i, j)` and `(j, i)` have the same offset, so every index is valid. The same layout
+/// serves Hermitian matrices, whose mirrored elements are conjugates rather than equal;
+/// [`mirrors`](PackedSymmetric::mirrors) says when an element must be conjugated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackedSymmetric {
+    shape: [usize; 2],
+    triangle: Triangle,
+}
+
+/// A Hermitian matrix is laid out just like a symmetric one.
+pub type PackedHermitian = PackedSymmetric;
+
+impl PackedSymmetric {
+    /// Create a layout for an `n` × `n` matrix that stores `triangle`.
+    pub fn new(n: usize, triangle: Triangle) -> Self {
+        PackedSymmetric {
+            shape: [n, n],
+            triangle,
+        }
+    }
+
+    /// Which triangle is stored.
+    pub fn triangle(&self) -> Triangle {
+        self.triangle
+    }
+
+    /// The number of elements that are actually stored.
+    pub fn packed_size(&self) -> usize {
+        packed_size(self.shape[0])
+    }
+
+    /// Returns `true` if `index` is outside the stored triangle, and so resolves to
+    /// the element mirrored across the diagonal.
+    pub fn mirrors(&self, index: &[usize; 2]) -> bool {
+        !self.triangle.contains(*index)
+    }
+}
+
+// Both triangles share the packed storage, and the layout says as much.
+unsafe impl Layout for PackedSymmetric {
+    type Indexer = [usize; 2];
+
+    const NDIM: Option<usize> = Some(2);
+
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        check_bounds(self.shape[0], i);
+        let [row, col] = *i;
+        let index = if self.mirrors(i) { [col, row] } else { *i };
+        packed_offset(self.shape[0], self.triangle, index) as isize
+    }
+
+    fn ndim(&self) -> usize {
+        2
+    }
+
+    fn memory_extent(&self) -> Option<Range<isize>> {
+        contiguous_extent(packed_size_checked(self.shape[0]))
+    }
+
+    /// Returns `false` unless the matrix is at most 1 × 1, since `(i, j)` and `(j, i)`
+    /// share an element.
+    fn has_unique_offsets(&self) -> bool {
+        self.shape[0] <= 1
+    }
+
+    /// Returns `false`, since the shared elements are what make the matrix symmetric.
+    fn expects_unique_offsets(&self) -> bool {
+        false
+    }
+
+    fn slice(&self) -> &[usize] {
+        &self.shape
+    }
+}
+
+/// The number of elements in one triangle of an `n` × `n` matrix, including the diagonal.
+fn packed_size(n: usize) -> usize {
+    n * (n + 1) / 2
+}
+
+/// [`packed_size`], or `None` if it overflows.
+fn packed_size_checked(n: usize) -> Option<usize> {
+    // Halve whichever factor is even first, so that only the product can overflow
+    if n.is_multiple_of(2) {
+        (n / 2).checked_mul(n + 1)
+    } else {
+        n.checked_mul(n / 2 + 1)
+    }
+}
+
+/// The offset of `(i, j)` within `triangle`, which must contain it.
+fn packed_offset(n: usize, triangle: Triangle, [i, j]: [usize; 2]) -> usize {
+    match triangle {
+        Triangle::Upper => i + j * (j + 1) / 2,
+        Triangle::Lower => i + j * (2 * n - j - 1) / 2,
+    }
+}
+
+/// **Panics** if `index` is out of bounds for an `n` × `n` matrix.
+fn check_bounds(n: usize, index: &[usize; 2]) {
+    for (axis, &ix) in index.iter().enumerate() {
+        assert!(
+            ix < n,
+            "index {ix} is out of bounds for axis {axis} with length {n}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::layout::testing::sorted_offsets;
+
+    #[test]
+    fn triangular_offsets_are_a_bijection() {
+        for triangle in [Triangle::Upper, Triangle::Lower] {
+            let layout = PackedTriangular::new(5, triangle);
+            assert_eq!(sorted_offsets(&layout), (0..15).collect::<Vec<_>>());
+            assert_eq!(layout.memory_extent(), Some(0..15));
+        }
+    }
+
+    #[test]
+    fn triangular_offsets_follow_lapack() {
+        let upper = PackedTriangular::new(3, Triangle::Upper);
+        assert_eq!(upper.try_offset(&[0, 2]), Some(3));
+        assert_eq!(upper.try_offset(&[1, 2]), Some(4));
+        assert_eq!(upper.try_offset(&[2, 0]), None);
+        let lower = PackedTriangular::new(3, Triangle::Lower);
+        assert_eq!(lower.try_offset(&[2, 0]), Some(2));
+        assert_eq!(lower.try_offset(&[1, 1]), Some(3));
+        assert_eq!(lower.try_offset(&[0, 2]), None);
+    }
+
+    #[test]
+    fn symmetric_offsets_are_a_bijection_on_the_stored_triangle() {
+        for triangle in [Triangle::Upper, Triangle::Lower] {
+            let layout = PackedSymmetric::new(4, triangle);
+            let mut stored = Vec::new();
+            for i in 0..4 {
+                for j in 0..4 {
+                    assert_eq!(layout.to_offset(&[i, j]), layout.to_offset(&[j, i]));
+                    if triangle.contains([i, j]) {
+                        stored.push(layout.to_offset(&[i, j]));
+                    }
+                }
+            }
+            stored.sort_unstable();
+            assert_eq!(stored, (0..10).collect::<Vec<_>>());
+            assert!(!layout.has_unique_offsets());
+            assert!(!layout.expects_unique_offsets());
+        }
+    }
+
+    #[test]
+    fn packed_size_overflow_is_caught() {
+        assert_eq!(packed_size_checked(4), Some(10));
+        assert_eq!(packed_size_checked(5), Some(15));
+        assert_eq!(packed_size_checked(usize::MAX), None);
+        assert_eq!(
+            PackedTriangular::new(usize::MAX, Triangle::Upper).memory_extent(),
+            None
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Pitched layouts, whose rows are padded so that each starts on an aligned boundary.
+//!
+//! Elements are laid out in row-major order, but the distance between the starts of
+//! consecutive rows (the *pitch*) is rounded up to a multiple of the alignment, as image
+//! buffers and SIMD kernels expect. The elements between the end of a row and the start of
+//! the next are padding: they belong to no index and are never visited.
+
+use std::{fmt, marker::PhantomData, mem, ops::Range};
+
+use super::{contiguous_extent, IndexAxis, InsertAxis, Layout, Strided};
+
+/// A row-major layout for elements of type `A`, whose rows each start on an `align`-byte
+/// boundary.
+///
+/// Checked constructors refuse to place the layout over elements of another size, or
+/// with an origin that is not itself aligned. An owned array is best created with
+/// [`Array::from_elem`](crate::core::Array::from_elem), which leaves room to align its
+/// origin; copies of it are aligned the same way. See the [module documentation](self)
+/// for more.
+pub struct Pitched<A, const N: usize> {
+    shape: [usize; N],
+    /// The distance between the starts of consecutive rows, in elements.
+    pitch: usize,
+    align: usize,
+    elem: PhantomData<fn() -> A>,
+}
+
+impl<A, const N: usize> Pitched<A, N> {
+    const CHECK: () = assert!(N > 0, "pitched layouts must have at least one axis");
+
+    /// Create a layout with the given shape, padding each row so that it starts on an
+    /// `align`-byte boundary.
+    ///
+    /// **Panics** if `align` is not a power of two, or is not a multiple of the size of `A`.
+    pub fn new(shape: [usize; N], align: usize) -> Self {
+        let () = Self::CHECK;
+        let elem_size = mem::size_of::<A>();
+        assert!(
+            align.is_power_of_two(),
+            "alignment must be a power of two, not {align}"
+        );
+        assert!(
+            elem_size != 0 && align.is_multiple_of(elem_size),
+            "alignment {align} is not a multiple of the element size {elem_size}"
+        );
+        let pitch = shape[N - 1].next_multiple_of(align / elem_size);
+        Pitched {
+            shape,
+            pitch,
+            align,
+            elem: PhantomData,
+        }
+    }
+
+    /// The length of each axis.
+    pub fn shape(&self) -> &[usize; N] {
+        &self.shape
+    }
+
+    /// The alignment of each row, in bytes.
+    pub fn align(&self) -> usize {
+        self.align
+    }
+
+    /// The distance between the starts of consecutive rows, in elements.
+    pub fn pitch(&self) -> usize {
+        self.pitch
+    }
+
+    /// The number of padding elements at the end of each row.
+    pub fn padding(&self) -> usize {
+        self.pitch - self.shape[N - 1]
+    }
+
+    /// The number of rows: the product of every axis length but the last.
+    pub fn nrows(&self) -> usize {
+        self.shape[..N - 1].iter().product()
+    }
+
+    /// The number of elements the layout spans, including the padding after every row.
+    pub fn padded_size(&self) -> usize {
+        self.nrows() * self.pitch
+    }
+
+    /// Return the strides of the layout, which are row-major but for the padded pitch.
+    pub fn strides(&self) -> [isize; N] {
+        let mut strides = [1; N];
+        let mut stride = self.pitch;
+        for (s, &len) in strides.iter_mut().zip(&self.shape).rev().skip(1) {
+            *s = stride as isize;
+            stride *= len;
+        }
+        strides
+    }
+
+    /// Return the offset at which each row starts, skipping the padding between them.
+    pub fn row_offsets(&self) -> impl Iterator<Item = usize> {
+        let pitch = self.pitch;
+        let rows = if self.shape[N - 1] == 0 {
+            0
+        } else {
+            self.nrows()
+        };
+        (0..rows).map(move |row| row * pitch)
+    }
+}
+
+// Derives would needlessly require `A` to implement each trait.
+impl<A, const N: usize> fmt::Debug for Pitched<A, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pitched")
+            .field("shape", &self.shape)
+            .field("pitch", &self.pitch)
+            .field("align", &self.align)
+            .finish()
+    }
+}
+
+impl<A, const N: usize> Clone for Pitched<A, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, const N: usize> Copy for Pitched<A, N> {}
+
+impl<A, const N: usize> PartialEq for Pitched<A, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.shape == other.shape && self.pitch == other.pitch && self.align == other.align
+    }
+}
+
+impl<A, const N: usize> Eq for Pitched<A, N> {}
+
+// Rows sit one pitch apart, and the extent covers every row along with its padding.
+unsafe impl<A, const N: usize> Layout for Pitched<A, N> {
+    type Indexer = [usize; N];
+
+    const NDIM: Option<usize> = Some(N);
+
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        for (axis, (&ix, &len)) in i.iter().zip(&self.shape).enumerate() {
+            assert!(
+                ix < len,
+                "index {ix} is out of bounds for axis {axis} with length {len}"
+            );
+        }
+        let row = i[..N - 1]
+            .iter()
+            .zip(&self.shape)
+            .fold(0, |offset, (&ix, &len)| offset * len + ix);
+        (row * self.pitch + i[N - 1]) as isize
+    }
+
+    fn ndim(&self) -> usize {
+        N
+    }
+
+    /// Every row, including its padding; see [`Pitched::padded_size`].
+    fn memory_extent(&self) -> Option<Range<isize>> {
+        let nrows = self.shape[..N - 1]
+            .iter()
+            .try_fold(1_usize, |n, &len| n.checked_mul(len));
+        contiguous_extent(nrows.and_then(|n| n.checked_mul(self.pitch)))
+    }
+
+    /// Returns `true` if the elements are the size of `A`, and `origin` is aligned to
+    /// [`Pitched::align`]. An empty layout has no rows to align.
+    fn is_aligned_for<E>(&self, origin: *const E) -> bool {
+        mem::size_of::<E>() == mem::size_of::<A>()
+            && (self.padded_size() == 0 || (origin as usize).is_multiple_of(self.align))
+    }
+
+    /// See [`Pitched::align`].
+    fn origin_alignment(&self) -> usize {
+        self.align
+    }
+
+    /// Returns `true` only if no padding separates one row from the next.
+    fn is_standard_layout(&self) -> bool {
+        self.padding() == 0 || self.nrows() <= 1 || self.shape.contains(&0)
+    }
+
+    fn slice(&self) -> &[usize] {
+        &self.shape
+    }
+}
+
+impl<A, const N: usize> From<Pitched<A, N>> for Strided<N> {
+    fn from(value: Pitched<A, N>) -> Self {
+        Strided::new(value.shape, value.strides())
+    }
+}
+
+/// Rows of the result are no longer padded to a common pitch, so it is strided.
+impl<A, const N: usize> IndexAxis for Pitched<A, N>
+where
+    Strided<N>: IndexAxis,
+{
+    type Smaller = <Strided<N> as IndexAxis>::Smaller;
+
+    fn index_axis(&self, axis: usize, index: usize) -> (Self::Smaller, isize) {
+        Strided::from(*self).index_axis(axis, index)
+    }
+}
+
+/// A new last axis would make every row one element long, so the result is strided.
+impl<A, const N: usize> InsertAxis for Pitched<A, N>
+where
+    Strided<N>: InsertAxis,
+{
+    type Larger = <Strided<N> as InsertAxis>::Larger;
+
+    fn insert_axis(&self, axis: usize) -> Self::Larger {
+        Strided::from(*self).insert_axis(axis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::layout::testing::sorted_offsets;
+
+    #[test]
+    fn pitch_rounds_rows_up_to_the_alignment() {
+        let layout = Pitched::<f32, 2>::new([3, 5], 32);
+        assert_eq!(layout.pitch(), 8);
+        assert_eq!(layout.padding(), 3);
+        assert_eq!(layout.padded_size(), 24);
+        assert_eq!(layout.strides(), [8, 1]);
+        assert_eq!(Pitched::<f32, 2>::new([3, 8], 32).pitch(), 8);
+        assert_eq!(Pitched::<u8, 3>::new([2, 3, 5], 4).strides(), [24, 8, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn alignment_must_fit_whole_elements() {
+        Pitched::<[u8; 3], 2>::new([2, 2], 4);
+    }
+
+    #[test]
+    fn offsets_skip_the_padding() {
+        let layout = Pitched::<f32, 2>::new([3, 5], 32);
+        assert_eq!(layout.to_offset(&[1, 0]), 8);
+        assert_eq!(layout.to_offset(&[2, 4]), 20);
+        let expected: Vec<isize> = [0, 8, 16]
+            .into_iter()
+            .flat_map(|row| row..row + 5)
+            .collect();
+        assert_eq!(sorted_offsets(&layout), expected);
+        assert_eq!(layout.memory_extent(), Some(0..24));
+        assert_eq!(layout.row_offsets().collect::<Vec<_>>(), [0, 8, 16]);
+        assert!(!layout.is_standard_layout());
+    }
+
+    #[test]
+    fn empty_rows_have_no_offsets() {
+        let layout = Pitched::<f32, 2>::new([3, 0], 32);
+        assert_eq!(layout.row_offsets().count(), 0);
+        assert!(sorted_offsets(&layout).is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+//! Slicing, indexing, and reversing the axes of strided layouts, without moving any elements.
+
+use std::ops::{Range, RangeFrom, RangeFull, RangeTo};
+
+use super::{
+    assert_axis_index, assert_insert_axis, AddAxis, IndexAxis, InsertAxis, RemoveAxis, Strided,
+};
+
+/// A slice of a single axis, like Python's `start:end:step`.
+///
+/// Negative `start` and `end` count back from the end of the axis. A negative `step`
+/// takes the elements of `start..end` in reverse, so `Slice::from(..).step_by(-1)`
+/// reverses the whole axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slice {
+    /// The first index in the slice, counting back from the end if negative.
+    pub start: isize,
+    /// One past the last index in the slice, counting back from the end if negative,
+    /// or `None` for the end of the axis.
+    pub end: Option<isize>,
+    /// The distance between consecutive elements of the slice; must not be zero.
+    pub step: isize,
+}
+
+impl Slice {
+    /// Create a new slice.
+    ///
+    /// **Panics** if `step` is zero.
+    pub fn new(start: isize, end: Option<isize>, step: isize) -> Self {
+        assert_ne!(step, 0, "slice step cannot be zero");
+        Slice { start, end, step }
+    }
+
+    /// Multiply the step of the slice by `step`.
+    ///
+    /// **Panics** if `step` is zero.
+    pub fn step_by(self, step: isize) -> Self {
+        Slice::new(self.start, self.end, self.step * step)
+    }
+}
+
+impl From<RangeFull> for Slice {
+    fn from(_value: RangeFull) -> Self {
+        Slice::new(0, None, 1)
+    }
+}
+
+impl From<Range<isize>> for Slice {
+    fn from(value: Range<isize>) -> Self {
+        Slice::new(value.start, Some(value.end), 1)
+    }
+}
+
+impl From<RangeFrom<isize>> for Slice {
+    fn from(value: RangeFrom<isize>) -> Self {
+        Slice::new(value.start, None, 1)
+    }
+}
+
+impl From<RangeTo<isize>> for Slice {
+    fn from(value: RangeTo<isize>) -> Self {
+        Slice::new(0, Some(value.end), 1)
+    }
+}
+
+impl<const N: usize> Strided<N> {
+    /// Reverse the order of the elements along `axis`.
+    ///
+    /// The element at index zero moves, so this returns the offset of the new origin
+    /// from the old one.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn invert_axis(&mut self, axis: usize) -> isize {
+        let len = self.shape[axis];
+        let stride = self.strides[axis];
+        self.strides[axis] = -stride;
+        if len == 0 {
+            0
+        } else {
+            (len - 1) as isize * stride
+        }
+    }
+
+    /// Restrict `axis` to `slice`.
+    ///
+    /// The element at index zero moves, so this returns the offset of the new origin
+    /// from the old one.
+    ///
+    /// **Panics** if `axis` is out of bounds, or if `slice` starts or ends out of bounds.
+    pub fn slice_axis(&mut self, axis: usize, slice: Slice) -> isize {
+        let len = self.shape[axis];
+        let stride = self.strides[axis];
+        let start = abs_index(len, slice.start);
+        let end = abs_index(len, slice.end.unwrap_or(len as isize)).max(start);
+        assert!(
+            end <= len,
+            "slice {start}..{end} is out of bounds for axis {axis} with length {len}"
+        );
+
+        let n = end - start;
+        let offset = if n == 0 {
+            0
+        } else if slice.step < 0 {
+            (end - 1) as isize * stride
+        } else {
+            start as isize * stride
+        };
+        self.shape[axis] = n.div_ceil(slice.step.unsigned_abs());
+        self.strides[axis] = stride * slice.step;
+        offset
+    }
+}
+
+impl<const N: usize> IndexAxis for Strided<N>
+where
+    Strided<N>: RemoveAxis,
+{
+    type Smaller = <Self as RemoveAxis>::Smaller;
+
+    fn index_axis(&self, axis: usize, index: usize) -> (Self::Smaller, isize) {
+        assert_axis_index(&self.shape, axis, index);
+        (self.remove_axis(axis), index as isize * self.strides[axis])
+    }
+}
+
+impl<const N: usize> InsertAxis for Strided<N>
+where
+    Strided<N>: AddAxis,
+{
+    type Larger = <Self as AddAxis>::Larger;
+
+    fn insert_axis(&self, axis: usize) -> Self::Larger {
+        assert_insert_axis(axis, N);
+        self.add_axis(axis, 1)
+    }
+}
+
+/// Convert a possibly negative `index` into an index from the start of an axis of length `len`.
+///
+/// **Panics** if a negative `index` reaches back past the start of the axis.
+fn abs_index(len: usize, index: isize) -> usize {
+    if index < 0 {
+        len.checked_sub(index.unsigned_abs()).unwrap_or_else(|| {
+            panic!("index {index} is out of bounds for an axis with length {len}")
+        })
+    } else {
+        index as usize
+    }
+}
@@ -0,0 +1,399 @@
+//! Strided layouts
+
+use std::ops::Range;
+
+use super::{
+    add_axis_into, assert_permutation, contiguous_strides, remove_axis_into, AddAxis, Layout,
+    Order, PermuteAxes, RemoveAxis,
+};
+
+/// A fixed-rank layout described by a shape and a stride for each axis.
+///
+/// Strides are counted in elements, not bytes, and are signed so that an axis
+/// can be walked backwards; an index along a negatively strided axis lies before
+/// the origin, at a negative offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Strided<const N: usize> {
+    pub(crate) shape: [usize; N],
+    pub(crate) strides: [isize; N],
+}
+
+impl<const N: usize> Strided<N> {
+    /// Create a layout from a shape and the stride of each of its axes.
+    pub fn new(shape: [usize; N], strides: [isize; N]) -> Self {
+        Strided { shape, strides }
+    }
+
+    /// Create a contiguous layout with the given shape, laid out in `order`.
+    pub fn from_shape_order(shape: [usize; N], order: Order) -> Self {
+        let mut strides = [0; N];
+        contiguous_strides(&shape, order, &mut strides);
+        Strided { shape, strides }
+    }
+
+    /// The length of each axis.
+    pub fn shape(&self) -> &[usize; N] {
+        &self.shape
+    }
+
+    /// The distance, in elements, between consecutive indices along each axis.
+    pub fn strides(&self) -> &[isize; N] {
+        &self.strides
+    }
+
+    /// Returns `true` if the layout is contiguous and in column-major ("Fortran") order.
+    ///
+    /// Axes of length one are ignored, since their stride is never used.
+    pub fn is_fortran_layout(&self) -> bool {
+        is_layout_in_order(&self.shape, &self.strides, Order::ColumnMajor)
+    }
+
+    /// Returns `true` if the elements occupy a contiguous block of memory, in any
+    /// order of the axes and with either sign of stride.
+    pub fn is_contiguous(&self) -> bool {
+        if self.is_standard_layout() {
+            return true;
+        }
+        if N == 1 {
+            // fast case for ndim == 1:
+            // Either we have length <= 1, then stride is arbitrary,
+            // or we have stride == 1 or stride == -1, but +1 case is already handled above.
+            return self.shape[0] <= 1 || self.strides[0] == -1;
+        }
+        let mut cstride = 1;
+        for i in self.fastest_varying_stride_order() {
+            // a dimension of length 1 can have unequal strides
+            if self.shape[i] != 1 && self.strides[i].unsigned_abs() != cstride {
+                return false;
+            }
+            cstride *= self.shape[i];
+        }
+        true
+    }
+
+    /// Return the axes ordered from fastest to slowest varying, i.e. by ascending
+    /// absolute stride.
+    ///
+    /// Axes with equal absolute strides keep their relative order.
+    pub fn fastest_varying_stride_order(&self) -> [usize; N] {
+        let mut indices = [0; N];
+        for (i, elt) in indices.iter_mut().enumerate() {
+            *elt = i;
+        }
+        indices.sort_by_key(|&i| self.strides[i].unsigned_abs());
+        indices
+    }
+
+    /// Return the fastest-varying axis: the one with the smallest absolute stride,
+    /// among the axes whose length is greater than one.
+    ///
+    /// If no axis is longer than one, returns the last axis.
+    ///
+    /// **Panics** if the layout has no dimensions.
+    pub fn min_stride_axis(&self) -> usize {
+        let n = match N {
+            0 => panic!("min_stride_axis: Array must have ndim > 0"),
+            1 => return 0,
+            n => n,
+        };
+        (0..n)
+            .rev()
+            .filter(|&ax| self.shape[ax] > 1)
+            .min_by_key(|&ax| self.strides[ax].unsigned_abs())
+            .unwrap_or(n - 1)
+    }
+
+    /// Return the slowest-varying axis: the one with the largest absolute stride,
+    /// among the axes whose length is greater than one.
+    ///
+    /// If no axis is longer than one, returns the first axis.
+    ///
+    /// **Panics** if the layout has no dimensions.
+    pub fn max_stride_axis(&self) -> usize {
+        match N {
+            0 => panic!("max_stride_axis: Array must have ndim > 0"),
+            1 => return 0,
+            _ => {}
+        }
+        (0..N)
+            .filter(|&ax| self.shape[ax] > 1)
+            .max_by_key(|&ax| self.strides[ax].unsigned_abs())
+            .unwrap_or(0)
+    }
+}
+
+// The extent and uniqueness are worked out from the strides themselves.
+unsafe impl<const N: usize> Layout for Strided<N> {
+    type Indexer = [usize; N];
+
+    const NDIM: Option<usize> = Some(N);
+
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        strided_offset(&self.shape, &self.strides, i)
+    }
+
+    fn ndim(&self) -> usize {
+        N
+    }
+
+    /// Runs from the element with the most negative offset to the one with the most
+    /// positive offset, so it starts before the origin if any stride is negative.
+    fn memory_extent(&self) -> Option<Range<isize>> {
+        strided_extent(&self.shape, &self.strides)
+    }
+
+    /// Returns `true` if, taking the axes from smallest to largest absolute stride,
+    /// each stride steps past every element reachable along the axes before it.
+    ///
+    /// This rejects broadcast axes, with a stride of zero, but also some interleaved
+    /// layouts that never alias, such as a shape of `[3, 2]` with strides `[2, 3]`.
+    fn has_unique_offsets(&self) -> bool {
+        strided_unique_offsets(
+            &self.shape,
+            &self.strides,
+            self.fastest_varying_stride_order(),
+        )
+    }
+
+    fn is_standard_layout(&self) -> bool {
+        is_layout_in_order(&self.shape, &self.strides, Order::RowMajor)
+    }
+
+    /// A row-major layout of the same shape.
+    fn compacted(&self) -> Option<Self> {
+        Some(Strided::from_shape_order(self.shape, Order::RowMajor))
+    }
+
+    fn slice(&self) -> &[usize] {
+        &self.shape
+    }
+}
+
+impl<const N: usize> PermuteAxes for Strided<N> {
+    type Permuted = Self;
+
+    fn permuted_axes(&self, axes: &[usize]) -> Self {
+        assert_permutation(axes, N);
+        Strided {
+            shape: std::array::from_fn(|i| self.shape[axes[i]]),
+            strides: std::array::from_fn(|i| self.strides[axes[i]]),
+        }
+    }
+}
+
+macro_rules! impl_remove_axis {
+    ($n:literal, $smaller:literal) => {
+        impl RemoveAxis for Strided<$n> {
+            type Smaller = Strided<$smaller>;
+
+            fn remove_axis(&self, axis: usize) -> Self::Smaller {
+                let mut smaller = Strided::new([0; $smaller], [0; $smaller]);
+                remove_axis_into(&self.shape, axis, &mut smaller.shape);
+                remove_axis_into(&self.strides, axis, &mut smaller.strides);
+                smaller
+            }
+        }
+    };
+}
+
+macro_rules! impl_add_axis {
+    ($n:literal, $larger:literal) => {
+        impl AddAxis for Strided<$n> {
+            type Larger = Strided<$larger>;
+
+            /// The new axis has a stride of zero, so if `length` is greater than one
+            /// it repeats the existing elements, as a broadcast axis does.
+            fn add_axis(&self, axis: usize, length: usize) -> Self::Larger {
+                let mut larger = Strided::new([0; $larger], [0; $larger]);
+                add_axis_into(&self.shape, axis, length, &mut larger.shape);
+                add_axis_into(&self.strides, axis, 0, &mut larger.strides);
+                larger
+            }
+        }
+    };
+}
+
+impl_remove_axis!(1, 0);
+impl_remove_axis!(2, 1);
+impl_remove_axis!(3, 2);
+impl_remove_axis!(4, 3);
+impl_remove_axis!(5, 4);
+impl_remove_axis!(6, 5);
+
+impl_add_axis!(0, 1);
+impl_add_axis!(1, 2);
+impl_add_axis!(2, 3);
+impl_add_axis!(3, 4);
+impl_add_axis!(4, 5);
+impl_add_axis!(5, 6);
+
+/// The offset of index `i` into `shape`, laid out with `strides`.
+///
+/// **Panics** if `i` does not have one entry per axis, or if any entry is out of bounds.
+pub(super) fn strided_offset(shape: &[usize], strides: &[isize], i: &[usize]) -> isize {
+    assert_eq!(
+        i.len(),
+        shape.len(),
+        "index has {} entries, but the layout has {} dimensions",
+        i.len(),
+        shape.len()
+    );
+    let mut offset = 0_isize;
+    for (axis, (&ix, (&len, &stride))) in i.iter().zip(shape.iter().zip(strides)).enumerate() {
+        assert!(
+            ix < len,
+            "index {ix} is out of bounds for axis {axis} with length {len}"
+        );
+        offset += ix as isize * stride;
+    }
+    offset
+}
+
+/// The offsets spanned by `shape` laid out with `strides`; see [`Strided::memory_extent`].
+pub(super) fn strided_extent(shape: &[usize], strides: &[isize]) -> Option<Range<isize>> {
+    if shape.contains(&0) {
+        return Some(0..0);
+    }
+    let (mut start, mut end) = (0_isize, 0_isize);
+    for (&len, &stride) in shape.iter().zip(strides) {
+        let reach = isize::try_from(len - 1).ok()?.checked_mul(stride)?;
+        if reach < 0 {
+            start = start.checked_add(reach)?;
+        } else {
+            end = end.checked_add(reach)?;
+        }
+    }
+    end = end.checked_add(1)?;
+    end.checked_sub(start)?;
+    Some(start..end)
+}
+
+/// Whether `shape` laid out with `strides` gives every index an offset of its own; see
+/// [`Strided::has_unique_offsets`].
+///
+/// `axes` must list every axis from smallest to largest absolute stride.
+pub(super) fn strided_unique_offsets(
+    shape: &[usize],
+    strides: &[isize],
+    axes: impl IntoIterator<Item = usize>,
+) -> bool {
+    if shape.contains(&0) {
+        return true;
+    }
+    let mut reach = 0_usize;
+    for axis in axes {
+        let len = shape[axis];
+        if len <= 1 {
+            continue;
+        }
+        let stride = strides[axis].unsigned_abs();
+        if stride <= reach {
+            return false;
+        }
+        reach = reach.saturating_add((len - 1).saturating_mul(stride));
+    }
+    true
+}
+
+/// Whether `strides` lay `shape` out contiguously in `order`, starting from the origin.
+///
+/// Axes of length one are ignored, and an empty shape is always in order.
+pub(super) fn is_layout_in_order(shape: &[usize], strides: &[isize], order: Order) -> bool {
+    if shape.contains(&0) {
+        return true;
+    }
+    let mut contig_stride = 1;
+    let mut in_order = |(&d, &s): (&usize, &isize)| {
+        if d == 1 {
+            return true;
+        }
+        let matches = s == contig_stride;
+        contig_stride *= d as isize;
+        matches
+    };
+    let mut axes = shape.iter().zip(strides);
+    match order {
+        Order::RowMajor => axes.rev().all(&mut in_order),
+        Order::ColumnMajor => axes.all(&mut in_order),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_strides_extend_before_the_origin() {
+        let layout = Strided::new([3, 4], [-4, 1]);
+        assert_eq!(layout.memory_extent(), Some(-8..4));
+        assert_eq!(layout.to_offset(&[2, 3]), -5);
+        assert!(layout.has_unique_offsets());
+        assert!(!layout.is_standard_layout());
+    }
+
+    #[test]
+    fn offsets_follow_each_layouts_own_strides() {
+        let c = Strided::new([2, 3], [3, 1]);
+        let f = Strided::new([2, 3], [1, 2]);
+        assert_eq!(c.to_offset(&[1, 2]), 5);
+        assert_eq!(f.to_offset(&[1, 2]), 5);
+        assert_eq!(c.to_offset(&[1, 0]), 3);
+        assert_eq!(f.to_offset(&[1, 0]), 1);
+        assert_eq!(Strided::new([2, 3], [10, 3]).to_offset(&[1, 2]), 16);
+        assert_eq!(Strided::new([2, 3], [10, 3]).memory_extent(), Some(0..17));
+    }
+
+    #[test]
+    fn zero_and_overlapping_strides_repeat_offsets() {
+        assert!(!Strided::new([2, 3], [0, 1]).has_unique_offsets());
+        assert!(!Strided::new([2, 3], [2, 1]).has_unique_offsets());
+        assert!(Strided::new([1, 3], [0, 1]).has_unique_offsets());
+        assert!(Strided::new([2, 3], [-1, 2]).has_unique_offsets());
+    }
+
+    #[test]
+    fn contiguity_of_row_and_column_major_layouts() {
+        let c = Strided::from_shape_order([2, 3, 4], Order::RowMajor);
+        assert!(c.is_contiguous() && !c.is_fortran_layout());
+        assert_eq!(c.fastest_varying_stride_order(), [2, 1, 0]);
+        assert_eq!((c.min_stride_axis(), c.max_stride_axis()), (2, 0));
+
+        let f = Strided::from_shape_order([2, 3, 4], Order::ColumnMajor);
+        assert!(f.is_contiguous() && f.is_fortran_layout() && !f.is_standard_layout());
+        assert_eq!(f.fastest_varying_stride_order(), [0, 1, 2]);
+        assert_eq!((f.min_stride_axis(), f.max_stride_axis()), (0, 2));
+    }
+
+    #[test]
+    fn contiguity_in_any_axis_order_and_direction() {
+        let permuted = Strided::new([2, 3, 4], [1, 8, 2]);
+        assert!(permuted.is_contiguous() && !permuted.is_fortran_layout());
+        assert_eq!(permuted.fastest_varying_stride_order(), [0, 2, 1]);
+        assert_eq!(
+            (permuted.min_stride_axis(), permuted.max_stride_axis()),
+            (0, 1)
+        );
+
+        assert!(Strided::new([2, 3], [-3, -1]).is_contiguous());
+        assert!(Strided::new([3], [-1]).is_contiguous());
+        assert!(!Strided::new([2, 3], [4, 1]).is_contiguous());
+        assert!(!Strided::new([3], [2]).is_contiguous());
+    }
+
+    #[test]
+    fn axes_of_length_one_are_ignored() {
+        let row = Strided::new([1, 3], [100, 1]);
+        assert!(row.is_contiguous());
+        assert_eq!((row.min_stride_axis(), row.max_stride_axis()), (1, 1));
+        assert!(Strided::new([3, 1], [1, 100]).is_fortran_layout());
+
+        let single = Strided::new([1, 1], [5, 7]);
+        assert_eq!((single.min_stride_axis(), single.max_stride_axis()), (1, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "ndim > 0")]
+    fn min_stride_axis_needs_an_axis() {
+        Strided::new([], []).min_stride_axis();
+    }
+}
@@ -0,0 +1,11 @@
+//! Helpers shared by the layout tests.
+
+use super::Layout;
+
+/// Every offset of `layout`, sorted.
+pub(super) fn sorted_offsets<L: Layout>(layout: &L) -> Vec<isize> {
+    let mut offsets = Vec::new();
+    layout.for_each_offset(|offset| offsets.push(offset));
+    offsets.sort_unstable();
+    offsets
+}
@@ -0,0 +1,212 @@
+//! Tiled (blocked) layouts, which store an array as a sequence of small contiguous tiles.
+//!
+//! The array is cut into tiles of `T` elements along every axis. Tiles are stored one
+//! after another in row-major order of the tile grid, and the elements of each tile are
+//! stored contiguously, also in row-major order. When an axis length is not a multiple of
+//! `T`, the tiles along its far edge are ragged: they are only as long as the elements that
+//! remain, and are packed without any padding.
+
+use super::{contiguous_offset, Dense, Layout, Order};
+
+/// A layout that stores the array as contiguous tiles of `T` elements along each axis.
+///
+/// See the [module documentation](self) for how tiles are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tiled<const N: usize, const T: usize> {
+    shape: [usize; N],
+}
+
+/// A two-dimensional layout of `T` × `T` tiles.
+pub type Tiled2<const T: usize> = Tiled<2, T>;
+
+/// A three-dimensional layout of `T` × `T` × `T` tiles.
+pub type Tiled3<const T: usize> = Tiled<3, T>;
+
+impl<const N: usize, const T: usize> Tiled<N, T> {
+    const CHECK: () = assert!(
+        T > 0,
+        "tiles must have at least one element along each axis"
+    );
+
+    /// Create a tiled layout with the given shape.
+    pub fn new(shape: [usize; N]) -> Self {
+        let () = Self::CHECK;
+        Tiled { shape }
+    }
+
+    /// Create a tiled layout with the shape of `layout`.
+    ///
+    /// This is not a conversion: over the same elements, the tiled layout finds most of
+    /// them at different indices than `layout` does. It suits elements that are to be
+    /// written in tiled order, not ones already stored in row-major order.
+    pub fn over_dense_shape(layout: Dense<N>) -> Self {
+        Tiled::new(layout.0)
+    }
+
+    /// The length of each axis.
+    pub fn shape(&self) -> &[usize; N] {
+        &self.shape
+    }
+
+    /// The number of tiles along each axis, counting ragged edge tiles.
+    pub fn tile_counts(&self) -> [usize; N] {
+        self.shape.map(|len| len.div_ceil(T))
+    }
+
+    /// Return the tiles in the order they are stored.
+    pub fn tiles(&self) -> Tiles<N, T> {
+        Tiles {
+            layout: *self,
+            next: (!self.shape.contains(&0)).then_some([0; N]),
+            offset: 0,
+        }
+    }
+
+    /// The shape of the tile at `tile`, a position in the tile grid.
+    fn tile_shape(&self, tile: &[usize; N]) -> [usize; N] {
+        let mut shape = [0; N];
+        for ((ext, &len), &t) in shape.iter_mut().zip(&self.shape).zip(tile) {
+            *ext = (len - t * T).min(T);
+        }
+        shape
+    }
+}
+
+// Every in-bounds index has a slot of its own in exactly one tile, within `0..size`.
+unsafe impl<const N: usize, const T: usize> Layout for Tiled<N, T> {
+    type Indexer = [usize; N];
+
+    const NDIM: Option<usize> = Some(N);
+
+    fn to_offset(&self, i: &Self::Indexer) -> isize {
+        for (axis, (&ix, &len)) in i.iter().zip(&self.shape).enumerate() {
+            assert!(
+                ix < len,
+                "index {ix} is out of bounds for axis {axis} with length {len}"
+            );
+        }
+        let tile = i.map(|ix| ix / T);
+        let tile_shape = self.tile_shape(&tile);
+        // The tiles stored before this one are those that agree with it on the first `k`
+        // axes and come before it on axis `k`, for each `k`. Those tiles span the extent of
+        // this tile on the first `k` axes, `tile[k] * T` elements on axis `k`, and the whole
+        // of every later axis.
+        let mut offset = 0;
+        let mut later = 1;
+        for k in (0..N).rev() {
+            let earlier: usize = tile_shape[..k].iter().product();
+            offset += earlier * tile[k] * T * later;
+            later *= self.shape[k];
+        }
+        (offset + contiguous_offset(&tile_shape, &i.map(|ix| ix % T), Order::RowMajor)) as isize
+    }
+
+    fn ndim(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if every axis after the first fits in a single tile, in which case
+    /// the tiles are whole bands of rows and the layout is the same as [`Dense`].
+    fn is_standard_layout(&self) -> bool {
+        self.shape.contains(&0) || self.shape.iter().skip(1).all(|&len| len <= T)
+    }
+
+    fn slice(&self) -> &[usize] {
+        &self.shape
+    }
+}
+
+/// A single tile of a [`Tiled`] layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile<const N: usize> {
+    /// The index of the tile's first element in the whole array.
+    pub origin: [usize; N],
+    /// The offset of the tile's first element from the origin of the array.
+    pub offset: usize,
+    /// The layout of the elements within the tile, which are stored contiguously.
+    pub layout: Dense<N>,
+}
+
+/// An iterator over the tiles of a [`Tiled`] layout, in the order they are stored.
+///
+/// Created by [`Tiled::tiles`].
+#[derive(Debug, Clone)]
+pub struct Tiles<const N: usize, const T: usize> {
+    layout: Tiled<N, T>,
+    next: Option<[usize; N]>,
+    offset: usize,
+}
+
+impl<const N: usize, const T: usize> Iterator for Tiles<N, T> {
+    type Item = Tile<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let tile = self.next?;
+        let layout = Dense(self.layout.tile_shape(&tile));
+        let item = Tile {
+            origin: tile.map(|t| t * T),
+            offset: self.offset,
+            layout,
+        };
+        self.offset += layout.size();
+
+        // Step to the next tile in row-major order of the tile grid
+        let counts = self.layout.tile_counts();
+        let mut tile = tile;
+        self.next = None;
+        for (t, &count) in tile.iter_mut().zip(&counts).rev() {
+            *t += 1;
+            if *t < count {
+                self.next = Some(tile);
+                break;
+            }
+            *t = 0;
+        }
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::layout::testing::sorted_offsets;
+
+    #[test]
+    fn offsets_are_a_bijection_with_ragged_tiles() {
+        let layout = Tiled::<2, 3>::new([5, 7]);
+        assert_eq!(sorted_offsets(&layout), (0..35).collect::<Vec<_>>());
+        let layout = Tiled::<3, 2>::new([3, 4, 5]);
+        assert_eq!(sorted_offsets(&layout), (0..60).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn tiles_are_contiguous_and_in_order() {
+        let layout = Tiled::<2, 2>::new([3, 3]);
+        let tiles: Vec<_> = layout.tiles().collect();
+        assert_eq!(tiles.len(), 4);
+        let mut next = 0;
+        for tile in tiles {
+            assert_eq!(tile.offset, next);
+            assert_eq!(layout.to_offset(&tile.origin), tile.offset as isize);
+            next += tile.layout.size();
+        }
+        assert_eq!(next, 9);
+    }
+
+    #[test]
+    fn narrow_layouts_are_standard() {
+        let layout = Tiled::<2, 4>::new([10, 4]);
+        assert!(layout.is_standard_layout());
+        assert_eq!(layout.to_offset(&[5, 2]), 22);
+        assert!(!Tiled::<2, 4>::new([10, 5]).is_standard_layout());
+    }
+
+    #[test]
+    fn a_dense_shape_is_not_a_dense_layout() {
+        let dense = Dense::new([4, 4]);
+        let layout = Tiled::<2, 2>::over_dense_shape(dense);
+        assert_eq!(layout, Tiled::new([4, 4]));
+        assert_eq!(layout.to_offset(&[0, 2]), 4);
+        assert_eq!(dense.to_offset(&[0, 2]), 2);
+    }
+}
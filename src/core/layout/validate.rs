@@ -0,0 +1,128 @@
+//! Checking that a layout can be safely placed over an allocation.
+
+use std::ops::Range;
+
+use crate::core::{ErrorKind, ShapeError};
+
+use super::Layout;
+
+/// Check that every element of `layout` lies within an allocation of `len` elements.
+///
+/// On success, returns where the origin of the layout must sit within the allocation:
+/// at its start, unless some elements lie before the origin, as with negative strides.
+///
+/// Fails if the number of elements or their extent overflows an `isize`, or if the
+/// layout needs more than `len` elements.
+pub fn validate_extent<L: Layout + ?Sized>(layout: &L, len: usize) -> Result<usize, ShapeError> {
+    let overflow = || ShapeError::from_kind(ErrorKind::Overflow);
+    let size = layout.size_checked().ok_or_else(overflow)?;
+    isize::try_from(size).map_err(|_| overflow())?;
+    let extent = layout.memory_extent().ok_or_else(overflow)?;
+    let required = extent.end.abs_diff(extent.start);
+    if required > len {
+        return Err(ShapeError::from_kind(ErrorKind::OutOfBounds {
+            required,
+            len,
+        }));
+    }
+    Ok(extent.start.unsigned_abs())
+}
+
+/// Check that no two valid indices of `layout` map to the same element, unless the layout
+/// means them to, as a symmetric one does; see [`Layout::expects_unique_offsets`].
+///
+/// This trusts [`Layout::has_unique_offsets`], and so may reject some layouts that do
+/// not alias.
+pub fn validate_unaliased<L: Layout + ?Sized>(layout: &L) -> Result<(), ShapeError> {
+    if !layout.expects_unique_offsets() || layout.has_unique_offsets() {
+        Ok(())
+    } else {
+        Err(ShapeError::from_kind(ErrorKind::AliasedElements))
+    }
+}
+
+/// Check that `layout` can have its origin at `origin`, as a layout with aligned rows
+/// requires; see [`Layout::is_aligned_for`].
+pub fn validate_alignment<L: Layout + ?Sized, A>(
+    layout: &L,
+    origin: *const A,
+) -> Result<(), ShapeError> {
+    if layout.is_aligned_for(origin) {
+        Ok(())
+    } else {
+        Err(ShapeError::from_kind(ErrorKind::Misaligned))
+    }
+}
+
+/// The extent of `size` elements laid out from the origin, or `None` if `size` is `None`
+/// or does not fit in an `isize`.
+pub(super) fn contiguous_extent(size: Option<usize>) -> Option<Range<isize>> {
+    let size = isize::try_from(size?).ok()?;
+    Some(0..size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Dense, PackedSymmetric, Pitched, Strided, Triangle};
+
+    #[test]
+    fn extent_places_the_origin_at_the_start() {
+        assert_eq!(validate_extent(&Dense::new([2, 3]), 6), Ok(0));
+        assert_eq!(validate_extent(&Dense::new([2, 3]), 10), Ok(0));
+    }
+
+    #[test]
+    fn extent_places_the_origin_after_elements_before_it() {
+        let layout = Strided::new([3, 4], [-4, 1]);
+        assert_eq!(validate_extent(&layout, 12), Ok(8));
+        let layout = Strided::new([3, 4], [-4, -1]);
+        assert_eq!(validate_extent(&layout, 12), Ok(11));
+    }
+
+    #[test]
+    fn extent_rejects_a_short_allocation() {
+        let err = validate_extent(&Strided::new([3, 4], [-4, 1]), 11).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &ErrorKind::OutOfBounds {
+                required: 12,
+                len: 11
+            }
+        );
+    }
+
+    #[test]
+    fn extent_rejects_an_overflowing_size() {
+        let err = validate_extent(&Dense::new([usize::MAX, 2]), usize::MAX).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Overflow);
+        let err = validate_extent(&Dense::new([usize::MAX / 2 + 1]), usize::MAX).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Overflow);
+    }
+
+    #[test]
+    fn unaliased_rejects_shared_offsets_only_where_unexpected() {
+        assert_eq!(validate_unaliased(&Strided::new([2, 3], [3, 1])), Ok(()));
+        let err = validate_unaliased(&Strided::new([2, 3], [0, 1])).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::AliasedElements);
+        assert_eq!(
+            validate_unaliased(&PackedSymmetric::new(3, Triangle::Upper)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn alignment_rejects_a_misaligned_origin() {
+        #[repr(align(32))]
+        struct Aligned([f32; 16]);
+        let data = Aligned([0.0; 16]);
+        let layout = Pitched::<f32, 2>::new([2, 3], 32);
+        assert_eq!(validate_alignment(&layout, data.0.as_ptr()), Ok(()));
+        let err = validate_alignment(&layout, data.0[1..].as_ptr()).unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Misaligned);
+        assert_eq!(
+            validate_alignment(&Dense::new([2, 3]), data.0[1..].as_ptr()),
+            Ok(())
+        );
+    }
+}
@@ -1,19 +1,277 @@
 //! Ownership types
 
-use std::{mem, ptr::NonNull, sync::Arc};
+use std::{
+    fmt,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ptr::NonNull,
+    rc::Rc,
+    sync::Arc,
+};
+
+use super::storage::{InlineStorage, NonNullStorage};
 
-#[derive(Debug)]
 pub struct VecOwner<A> {
     pub(crate) ptr: NonNull<A>,
     pub(crate) len: usize,
     pub(crate) cap: usize,
 }
 
-#[derive(Debug)]
+// A `VecOwner` owns its elements just as a `Vec` does.
+unsafe impl<A: Send> Send for VecOwner<A> {}
+unsafe impl<A: Sync> Sync for VecOwner<A> {}
+
 pub struct ArcOwner<A>(pub(crate) Arc<VecOwner<A>>);
 
-impl<A> Clone for VecOwner<A> {
+pub struct RcOwner<A>(pub(crate) Rc<VecOwner<A>>);
+
+/// Up to `CAP` elements kept inline, of which the first `len` are initialized.
+pub struct StackOwner<A, const CAP: usize> {
+    pub(crate) elems: [MaybeUninit<A>; CAP],
+    pub(crate) len: usize,
+}
+
+/// Elements that are either borrowed for `'a` or owned.
+pub enum CowOwner<'a, A> {
+    /// The span of another array's elements that a view covers.
+    ///
+    /// This is kept as a pointer, not a slice: the span may include elements the view
+    /// does not cover, which can be borrowed mutably elsewhere.
+    View {
+        ptr: NonNull<A>,
+        len: usize,
+        life: PhantomData<&'a A>,
+    },
+    Owned(VecOwner<A>),
+}
+
+// A borrowed `CowOwner` only reads the elements the view covers, like a `&'a A` would.
+unsafe impl<A: Send + Sync> Send for CowOwner<'_, A> {}
+unsafe impl<A: Sync> Sync for CowOwner<'_, A> {}
+
+impl<A> fmt::Debug for VecOwner<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VecOwner")
+            .field("ptr", &self.ptr)
+            .field("len", &self.len)
+            .field("cap", &self.cap)
+            .finish()
+    }
+}
+
+impl<A> fmt::Debug for ArcOwner<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArcOwner").field(&self.0).finish()
+    }
+}
+
+impl<A> fmt::Debug for RcOwner<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RcOwner").field(&self.0).finish()
+    }
+}
+
+impl<A, const CAP: usize> fmt::Debug for StackOwner<A, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StackOwner")
+            .field("len", &self.len)
+            .field("cap", &CAP)
+            .finish()
+    }
+}
+
+impl<A> fmt::Debug for CowOwner<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CowOwner::View { ptr, len, .. } => f
+                .debug_struct("View")
+                .field("ptr", ptr)
+                .field("len", len)
+                .finish(),
+            CowOwner::Owned(owner) => f.debug_tuple("Owned").field(owner).finish(),
+        }
+    }
+}
+
+impl<A> From<Vec<A>> for VecOwner<A> {
+    /// Take over the allocation of `v`, without moving its elements.
+    fn from(v: Vec<A>) -> Self {
+        let mut v = ManuallyDrop::new(v);
+        VecOwner {
+            // A `Vec`'s pointer is never null, even when it has not allocated.
+            ptr: unsafe { NonNull::new_unchecked(v.as_mut_ptr()) },
+            len: v.len(),
+            cap: v.capacity(),
+        }
+    }
+}
+
+impl<A> VecOwner<A> {
+    /// Give the allocation back as the `Vec` it came from.
+    pub(crate) fn into_vec(self) -> Vec<A> {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: the parts came from a `Vec`, and `this` will not free them again.
+        unsafe { Vec::from_raw_parts(this.ptr.as_ptr(), this.len, this.cap) }
+    }
+
+    /// Return storage whose origin is `offset` elements from the start of the allocation.
+    ///
+    /// **Panics** if `offset` is not within the allocation, or one past its end.
+    pub(crate) fn storage_at(&self, offset: isize) -> NonNullStorage<A> {
+        storage_at(self.ptr, self.len, offset)
+    }
+
+    /// Return how many elements `ptr` lies past the start of the allocation.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must lie within the allocation, or one past its end.
+    pub(crate) unsafe fn offset_of(&self, ptr: NonNull<A>) -> isize {
+        offset_of(self.ptr, ptr)
+    }
+
+    /// Borrow the elements as a slice.
+    pub(crate) fn as_slice(&self) -> &[A] {
+        // SAFETY: the first `len` elements of the allocation are initialized.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+/// Copies every element into a new allocation of the same length.
+impl<A: Clone> Clone for VecOwner<A> {
+    fn clone(&self) -> Self {
+        VecOwner::from(self.as_slice().to_vec())
+    }
+}
+
+impl<A> Drop for VecOwner<A> {
+    fn drop(&mut self) {
+        // SAFETY: the parts came from a `Vec`, and are never used again.
+        drop(unsafe { Vec::from_raw_parts(self.ptr.as_ptr(), self.len, self.cap) });
+    }
+}
+
+impl<A> CowOwner<'_, A> {
+    /// Return storage whose origin is `offset` elements from the start of the elements.
+    ///
+    /// **Panics** if `offset` is not within the elements, or one past their end.
+    pub(crate) fn storage_at(&self, offset: isize) -> NonNullStorage<A> {
+        match self {
+            // Borrowed elements are only ever read; they are copied before any mutation.
+            CowOwner::View { ptr, len, .. } => storage_at(*ptr, *len, offset),
+            CowOwner::Owned(owner) => owner.storage_at(offset),
+        }
+    }
+}
+
+impl<A, const CAP: usize> StackOwner<A, CAP> {
+    /// Copy `elems` into a new owner.
+    ///
+    /// **Panics** if there are more than `CAP` of them.
+    pub(crate) fn from_slice(elems: &[A]) -> Self
+    where
+        A: Clone,
+    {
+        Self::from_fn(elems.len(), |i| elems[i].clone())
+    }
+
+    /// Create an owner of `len` elements, calling `f` with the index of each.
+    ///
+    /// **Panics** if `len` is greater than `CAP`.
+    pub(crate) fn from_fn(len: usize, mut f: impl FnMut(usize) -> A) -> Self {
+        assert!(
+            len <= CAP,
+            "{len} elements do not fit in a capacity of {CAP}"
+        );
+        let mut owner = StackOwner {
+            elems: [const { MaybeUninit::uninit() }; CAP],
+            len: 0,
+        };
+        for (i, elem) in owner.elems[..len].iter_mut().enumerate() {
+            elem.write(f(i));
+            // Count each element as it is written, so that a panic in `f` drops only these
+            owner.len = i + 1;
+        }
+        owner
+    }
+
+    /// Return storage whose origin is `offset` elements from the start of the elements.
+    ///
+    /// The storage may write to the elements, but only until the owner moves.
+    ///
+    /// **Panics** if `offset` is not within the elements, or one past their end.
+    pub(crate) fn storage_at(&mut self, offset: isize) -> InlineStorage<A> {
+        assert!(
+            (0..=self.len as isize).contains(&offset),
+            "offset {offset} is out of bounds for {} elements",
+            self.len
+        );
+        InlineStorage::new(self.base_mut(), offset)
+    }
+
+    /// Return a pointer to the first element at the owner's current address, which may
+    /// only be read through.
+    pub(crate) fn base(&self) -> NonNull<A> {
+        NonNull::from(&self.elems).cast()
+    }
+
+    /// Return a pointer to the first element at the owner's current address, which may
+    /// be written through.
+    pub(crate) fn base_mut(&mut self) -> NonNull<A> {
+        // SAFETY: the pointer comes from a reference, so is never null.
+        unsafe { NonNull::new_unchecked(self.elems.as_mut_ptr().cast()) }
+    }
+
+    /// Borrow the initialized elements as a slice.
+    pub(crate) fn as_slice(&self) -> &[A] {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { std::slice::from_raw_parts(self.elems.as_ptr().cast(), self.len) }
+    }
+}
+
+/// Copies every initialized element.
+impl<A: Clone, const CAP: usize> Clone for StackOwner<A, CAP> {
     fn clone(&self) -> Self {
-        todo!()
+        StackOwner::from_slice(self.as_slice())
+    }
+}
+
+impl<A, const CAP: usize> Drop for StackOwner<A, CAP> {
+    fn drop(&mut self) {
+        // SAFETY: the first `len` elements are initialized, and are never used again.
+        unsafe {
+            std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(
+                self.elems.as_mut_ptr().cast::<A>(),
+                self.len,
+            ))
+        }
+    }
+}
+
+/// Return storage `offset` elements past `base`, which points to `len` elements.
+///
+/// The storage is derived from `base` itself, so it may write wherever `base` may.
+///
+/// **Panics** if `offset` is not within the elements, or one past their end.
+fn storage_at<A>(base: NonNull<A>, len: usize, offset: isize) -> NonNullStorage<A> {
+    assert!(
+        (0..=len as isize).contains(&offset),
+        "offset {offset} is out of bounds for {len} elements"
+    );
+    NonNullStorage {
+        // SAFETY: `offset` is within the elements, or one past their end.
+        ptr: unsafe { base.offset(offset) },
+    }
+}
+
+/// # Safety
+///
+/// `ptr` must lie within the elements starting at `base`, or one past their end.
+unsafe fn offset_of<A>(base: NonNull<A>, ptr: NonNull<A>) -> isize {
+    if mem::size_of::<A>() == 0 {
+        // Every element of a zero-sized type is at the same address
+        0
+    } else {
+        ptr.offset_from(base)
     }
 }
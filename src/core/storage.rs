@@ -1,11 +1,37 @@
 //! Storage types
 
-use std::ptr::NonNull;
+use std::{
+    fmt,
+    marker::PhantomData,
+    ptr::NonNull,
+    sync::atomic::{AtomicPtr, Ordering},
+};
 
 use super::{PointerStorage, Storage};
 
 pub struct NonNullStorage<T> {
-    ptr: NonNull<T>,
+    pub(crate) ptr: NonNull<T>,
+}
+
+impl<T> Clone for NonNullStorage<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NonNullStorage<T> {}
+
+// The storage stands in for the elements it points to; whether it may also write to them,
+// or share them, is up to the array types that hold it.
+unsafe impl<T: Send> Send for NonNullStorage<T> {}
+unsafe impl<T: Sync> Sync for NonNullStorage<T> {}
+
+impl<T> fmt::Debug for NonNullStorage<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NonNullStorage")
+            .field("ptr", &self.ptr)
+            .finish()
+    }
 }
 
 impl<T> Storage for NonNullStorage<T> {
@@ -13,15 +39,98 @@ impl<T> Storage for NonNullStorage<T> {
 }
 
 impl<T> PointerStorage for NonNullStorage<T> {
-    unsafe fn ref_from_offset(&self, offset: usize) -> &T {
-        self.ptr.add(offset).as_ref()
+    unsafe fn ref_from_offset(&self, offset: isize) -> &T {
+        self.ptr.offset(offset).as_ref()
     }
 
-    unsafe fn ref_mut_from_offset(&mut self, offset: usize) -> &mut T {
-        self.ptr.add(offset).as_mut()
+    unsafe fn ref_mut_from_offset(&mut self, offset: isize) -> &mut T {
+        self.ptr.offset(offset).as_mut()
     }
 
     unsafe fn as_ptr(&self) -> *mut Self::Elem {
         self.ptr.as_ptr()
     }
+
+    unsafe fn offset(&self, offset: isize) -> Self {
+        NonNullStorage {
+            ptr: self.ptr.offset(offset),
+        }
+    }
+}
+
+/// Storage for elements kept inline in their owner, which move whenever it does.
+///
+/// The origin is kept as an offset from the start of the elements, and the start itself is
+/// re-derived from the owner on every access; see [`Backend::refresh`](super::Backend::refresh).
+/// A shared access may have to update it, so it is kept in an atomic, which needs no more
+/// than a plain load and store.
+pub struct InlineStorage<T> {
+    base: AtomicPtr<T>,
+    pub(crate) origin: isize,
+    phantom: PhantomData<NonNull<T>>,
+}
+
+impl<T> InlineStorage<T> {
+    pub(crate) fn new(base: NonNull<T>, origin: isize) -> Self {
+        InlineStorage {
+            base: AtomicPtr::new(base.as_ptr()),
+            origin,
+            phantom: PhantomData,
+        }
+    }
+
+    fn base(&self) -> NonNull<T> {
+        // SAFETY: the base only ever comes from a `NonNull`.
+        unsafe { NonNull::new_unchecked(self.base.load(Ordering::Relaxed)) }
+    }
+
+    /// Point the storage at elements starting at `base`, which may only be read through.
+    ///
+    /// Nothing is stored if the elements have not moved, so that a pointer that may write
+    /// to them is kept.
+    pub(crate) fn set_base(&self, base: NonNull<T>) {
+        if self.base() != base {
+            self.base.store(base.as_ptr(), Ordering::Relaxed);
+        }
+    }
+
+    /// Point the storage at elements starting at `base`, which may be written through.
+    pub(crate) fn set_base_mut(&mut self, base: NonNull<T>) {
+        *self.base.get_mut() = base.as_ptr();
+    }
+}
+
+impl<T> fmt::Debug for InlineStorage<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InlineStorage")
+            .field("base", &self.base())
+            .field("origin", &self.origin)
+            .finish()
+    }
+}
+
+// Like `NonNullStorage`, this stands in for the elements it points to.
+unsafe impl<T: Send> Send for InlineStorage<T> {}
+unsafe impl<T: Sync> Sync for InlineStorage<T> {}
+
+impl<T> Storage for InlineStorage<T> {
+    type Elem = T;
+}
+
+impl<T> PointerStorage for InlineStorage<T> {
+    unsafe fn ref_from_offset(&self, offset: isize) -> &T {
+        self.base().offset(self.origin + offset).as_ref()
+    }
+
+    unsafe fn ref_mut_from_offset(&mut self, offset: isize) -> &mut T {
+        self.base().offset(self.origin + offset).as_mut()
+    }
+
+    unsafe fn as_ptr(&self) -> *mut Self::Elem {
+        self.base().as_ptr().wrapping_offset(self.origin)
+    }
+
+    unsafe fn offset(&self, offset: isize) -> Self {
+        InlineStorage::new(self.base(), self.origin + offset)
+    }
 }
@@ -1,51 +1,6 @@
 use std::fmt::Debug;
 
-use super::ArrayBase;
-
-/// A trait representing how an array is laid out, including:
-///     1. Shape
-///     2. Type of index, i.e., the type for array[index_type]
-pub trait Layout {
-    /// The type used to index into the array.
-    type Indexer;
-
-    /// For fixed-size dimension representations, this should be
-    /// `Some(ndim)`, and for variable-sized representations this
-    /// should be `None`.
-    const NDIM: Option<usize>;
-
-    /// Convert from a reference to the `Indexer` type into a
-    /// linear offset from the "start" of the array.
-    ///
-    /// **Panics** if `i` is out of bounds for the array.
-    fn to_offset(i: &Self::Indexer) -> usize;
-
-    /// Returns the number of dimensions (number of axes).
-    fn ndim(&self) -> usize;
-
-    /// Compute the size of the dimension (number of elements)
-    fn size(&self) -> usize {
-        self.slice().iter().product()
-    }
-
-    /// Compute the size while checking for overflow.
-    fn size_checked(&self) -> Option<usize> {
-        self.slice()
-            .iter()
-            .try_fold(1_usize, |s, &a| s.checked_mul(a))
-    }
-
-    #[doc(hidden)]
-    fn slice(&self) -> &[usize];
-
-    #[doc(hidden)]
-    fn slice_mut(&mut self) -> &mut [usize];
-
-    #[doc(hidden)]
-    fn equal(&self, rhs: &Self) -> bool {
-        self.slice() == rhs.slice()
-    }
-}
+use super::{ArrayBase, Layout};
 
 /// A trait representing the underlying storage of the array.
 /// Its main job is to translate linear offsets (generated by
@@ -64,31 +19,53 @@ pub trait PointerStorage: Storage {
     unsafe fn as_ptr(&self) -> *mut Self::Elem;
 
     /// Get a reference to the element type from an offset.
-    /// The offset is the number of elements from the "origin" of the array.
+    /// The offset is the number of elements from the "origin" of the array,
+    /// and is negative for elements before it.
     ///
     /// # Safety
     ///
     /// The caller must ensure that the offset is within bounds of the array.
     /// This should be done in [`Layout::to_offset`], which should generate
     /// the input to this function.
-    unsafe fn ref_from_offset(&self, offset: usize) -> &Self::Elem;
+    unsafe fn ref_from_offset(&self, offset: isize) -> &Self::Elem;
 
     /// Get a mutable reference to the element type from an offset.
-    /// The offset is the number of elements from the "origin" of the array.
+    /// The offset is the number of elements from the "origin" of the array,
+    /// and is negative for elements before it.
     ///
     /// # Safety
     ///
     /// 1. The caller must ensure that the offset is within bounds of the array.
-    /// This should be done in [`Layout::to_offset`], which should generate
-    /// the input to this function.
+    ///    This should be done in [`Layout::to_offset`], which should generate
+    ///    the input to this function.
     ///
     /// 2. The caller must ensure that the data being accessed is uniquely held.
-    /// This should be done by
-    unsafe fn ref_mut_from_offset(&mut self, offset: usize) -> &mut Self::Elem;
+    ///    This should be done by [`Backend::ensure_unique`].
+    unsafe fn ref_mut_from_offset(&mut self, offset: isize) -> &mut Self::Elem;
+
+    /// Get storage whose "origin" is `offset` elements away from this one's.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the new origin is within bounds of the array,
+    /// or one past its end.
+    unsafe fn offset(&self, offset: isize) -> Self
+    where
+        Self: Sized;
 }
 
+/// The glue between the data an owning array holds and the references through
+/// which that data is read.
+///
+/// # Safety
+///
+/// Implementors must guarantee that the `Ref` produced by [`Backend::ref_from_owner_offset`]
+/// points into the allocation managed by `Owned`, and stays valid for as long as that
+/// allocation is neither dropped nor reallocated. If `Owned` keeps its elements inline,
+/// so that they move with it, [`Backend::refresh`] and [`Backend::refresh_mut`] must point
+/// the `Ref` back at them, the latter with a pointer that may write to them.
 pub unsafe trait Backend {
-    type Ref: Debug;
+    type Ref: PointerStorage<Elem = Self::Elem> + Debug;
     type Owned: Debug;
     type Elem;
 
@@ -107,4 +84,17 @@ pub unsafe trait Backend {
         Self: Sized;
 
     fn ref_from_owner_offset(owner: &mut Self::Owned, offset: isize) -> Self::Ref;
+
+    /// Point `storage` back at the elements held by `owner`, in case they have moved,
+    /// so that they can be read through it.
+    ///
+    /// An owning array calls this before every shared access to its elements. By default,
+    /// the elements never move, so there is nothing to do.
+    fn refresh(_owner: &Self::Owned, _storage: &Self::Ref) {}
+
+    /// Point `storage` back at the elements held by `owner`, in case they have moved,
+    /// so that they can be written through it.
+    ///
+    /// An owning array calls this before every mutable access to its elements.
+    fn refresh_mut(_owner: &mut Self::Owned, _storage: &mut Self::Ref) {}
 }
@@ -1,5 +1,6 @@
-mod core;
+pub mod core;
 
+#[allow(dead_code)]
 mod test_functions {
     //! ## Test Functions
     //!
@@ -9,9 +10,8 @@ mod test_functions {
     use core::fmt::Debug;
 
     use crate::core::{
-        Array, ArrayRef, ArrayView, ArrayViewBase, ArrayViewBaseMut, ArrayViewMut, Layout, NdArray,
-        RawArrayRef, RawArrayView, RawArrayViewBase, RawArrayViewBaseMut, RawArrayViewMut,
-        RawNdArray, Storage,
+        Array, ArrayRef, ArrayView, ArrayViewMut, Layout, RawArrayRef, RawArrayView,
+        RawArrayViewMut,
     };
 
     fn ergonomic_raw<A, L: Layout>(arr: &RawArrayRef<A, L>) {
@@ -30,8 +30,8 @@ mod test_functions {
     /// Scaffolding to call the above functions; arguments are move to simulate fully-owned values.
     fn caller<A: Debug, L: Layout>(
         mut arr: Array<A, L>,
-        arr_view: ArrayView<A, L>,
-        mut arr_view_mut: ArrayViewMut<A, L>,
+        arr_view: ArrayView<'_, A, L>,
+        mut arr_view_mut: ArrayViewMut<'_, A, L>,
         raw_view: RawArrayView<A, L>,
         mut raw_view_mut: RawArrayViewMut<A, L>,
     ) {